@@ -0,0 +1,369 @@
+//! `alnbind!`: compile-time binding of an ALN qpudatashard to typed Rust structs.
+//!
+//! The shard is parsed while the crate is compiled. Fields are partitioned by their
+//! `scope` column into the named structs, ALN datatypes are mapped to Rust types,
+//! and the shard's value block becomes each struct's `DEFAULT` const. Values that
+//! violate the schema (wrong type, missing required value, or a breach of the
+//! row's `min` / `max` / `equals` constraint columns) are compile errors, so an
+//! unsafe shard can never be built into a binary.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, Ident, LitStr, Token};
+
+use aln_shard::{AlnDatatype, AlnDocument, AlnScope, AlnValue};
+
+/// `enum_field name: path::Enum { "raw" => Variant, ..., _ => Fallback }`
+///
+/// Enums bound this way must implement every trait named in `derive(...)`.
+struct EnumField {
+    field: Ident,
+    ty: syn::Path,
+    arms: Vec<(LitStr, Ident)>,
+    fallback: Option<Ident>,
+}
+
+struct BindInput {
+    shard: LitStr,
+    module: Ident,
+    structs: BTreeMap<&'static str, Ident>,
+    enum_fields: Vec<EnumField>,
+    /// Extra derives for every generated struct, from `derive(Path, ...)`.
+    derives: Vec<syn::Path>,
+}
+
+const SCOPE_KEYS: [(&str, &str); 4] = [
+    ("profile_struct", "profile"),
+    ("policy_struct", "policy"),
+    ("state_struct", "state"),
+    ("metrics_struct", "metrics"),
+];
+
+impl Parse for EnumField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let field: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: syn::Path = input.parse()?;
+        let body;
+        braced!(body in input);
+        let mut arms = Vec::new();
+        let mut fallback = None;
+        while !body.is_empty() {
+            if body.peek(Token![_]) {
+                body.parse::<Token![_]>()?;
+                body.parse::<Token![=>]>()?;
+                fallback = Some(body.parse()?);
+            } else {
+                let raw: LitStr = body.parse()?;
+                body.parse::<Token![=>]>()?;
+                arms.push((raw, body.parse()?));
+            }
+            if !body.is_empty() {
+                body.parse::<Token![,]>()?;
+            }
+        }
+        Ok(EnumField {
+            field,
+            ty,
+            arms,
+            fallback,
+        })
+    }
+}
+
+impl Parse for BindInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut shard = None;
+        let mut module = None;
+        let mut structs = BTreeMap::new();
+        let mut enum_fields = Vec::new();
+        let mut derives = Vec::new();
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            let key_str = key.to_string();
+            match key_str.as_str() {
+                "shard" => shard = Some(input.parse::<LitStr>()?),
+                "module" => module = Some(input.parse::<Ident>()?),
+                "enum_field" => enum_fields.push(input.parse::<EnumField>()?),
+                "derive" => {
+                    let list;
+                    syn::parenthesized!(list in input);
+                    let paths = list.parse_terminated(syn::Path::parse, Token![,])?;
+                    derives.extend(paths);
+                }
+                other => match SCOPE_KEYS.iter().find(|(k, _)| *k == other) {
+                    Some((_, scope)) => {
+                        structs.insert(*scope, input.parse::<Ident>()?);
+                    }
+                    None => {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            format!("unknown alnbind key `{}`", other),
+                        ))
+                    }
+                },
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let shard = shard.ok_or_else(|| input.error("alnbind! requires `shard \"path\"`"))?;
+        let module = module.ok_or_else(|| input.error("alnbind! requires `module name`"))?;
+        Ok(BindInput {
+            shard,
+            module,
+            structs,
+            enum_fields,
+            derives,
+        })
+    }
+}
+
+#[proc_macro]
+pub fn alnbind(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as BindInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Resolve the shard path against the invoking crate, then each of its ancestors,
+/// so workspace-level `qpudatashards/` work from any member crate.
+fn resolve_shard(rel: &str) -> Option<PathBuf> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").ok()?;
+    Path::new(&manifest_dir)
+        .ancestors()
+        .map(|dir| dir.join(rel))
+        .find(|p| p.is_file())
+}
+
+fn expand(input: &BindInput) -> syn::Result<TokenStream2> {
+    let span = input.shard.span();
+    let rel = input.shard.value();
+    let path = resolve_shard(&rel)
+        .ok_or_else(|| syn::Error::new(span, format!("ALN shard `{}` not found", rel)))?;
+    let doc = AlnDocument::load(&path)
+        .map_err(|e| syn::Error::new(span, format!("{}: {}", rel, e)))?;
+    let schema = doc
+        .schema
+        .as_ref()
+        .ok_or_else(|| syn::Error::new(span, format!("{}: shard has no schema block", rel)))?;
+
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let mut report = |line: usize, msg: String| {
+        errors.push(syn::Error::new(span, format!("{}:{}: {}", rel, line, msg)));
+    };
+
+    for value in doc.values.iter().flat_map(|b| b.entries.iter()) {
+        if schema.field(&value.field).is_none() {
+            report(value.line, format!("value for undeclared field `{}`", value.field));
+        }
+    }
+
+    // Field declarations and const initializers, keyed by scope.
+    let mut by_scope: BTreeMap<&'static str, Vec<TokenStream2>> = BTreeMap::new();
+    let mut defaults: BTreeMap<&'static str, Vec<TokenStream2>> = BTreeMap::new();
+
+    for spec in &schema.fields {
+        let scope = match &spec.scope {
+            AlnScope::Profile => "profile",
+            AlnScope::Policy => "policy",
+            AlnScope::State => "state",
+            AlnScope::Metrics => "metrics",
+            other => {
+                report(spec.line, format!("field `{}` has unsupported scope {:?}", spec.name, other));
+                continue;
+            }
+        };
+        if !input.structs.contains_key(scope) {
+            report(
+                spec.line,
+                format!("field `{}` has scope `{}` but no `{}_struct` was given", spec.name, scope, scope),
+            );
+            continue;
+        }
+        let ident = match syn::parse_str::<Ident>(&spec.name) {
+            Ok(i) => i,
+            Err(_) => {
+                report(spec.line, format!("field `{}` is not a valid Rust identifier", spec.name));
+                continue;
+            }
+        };
+
+        let value = doc.value(&spec.name);
+        if spec.required && value.is_none() {
+            report(spec.line, format!("required field `{}` has no value", spec.name));
+            continue;
+        }
+        if let Some(v) = value {
+            if let Err(e) = v.check(spec.datatype) {
+                report(v.line, e.to_string());
+                continue;
+            }
+            if let Some(msg) = spec.constraint.violation(v) {
+                report(v.line, msg);
+                continue;
+            }
+        }
+
+        let enum_field = input.enum_fields.iter().find(|e| e.field == spec.name.as_str());
+        let (base_ty, expr) = match enum_field {
+            Some(ef) => {
+                let expr = match value {
+                    Some(v) => match enum_value(ef, v) {
+                        Some(t) => Some(t),
+                        None => {
+                            report(v.line, format!("`{}` = `{}` matches no enum_field arm", spec.name, v.raw));
+                            continue;
+                        }
+                    },
+                    None => None,
+                };
+                let ty = &ef.ty;
+                (quote!(#ty), expr)
+            }
+            None => (rust_type(spec.datatype), value.map(|v| literal(spec.datatype, v))),
+        };
+
+        let (ty, default) = if spec.required {
+            (base_ty, expr.expect("required value checked above"))
+        } else {
+            let d = match expr {
+                Some(e) => quote!(::core::option::Option::Some(#e)),
+                None => quote!(::core::option::Option::None),
+            };
+            (quote!(::core::option::Option<#base_ty>), d)
+        };
+
+        let doc_line = format!(" {}", spec.description);
+        by_scope.entry(scope).or_default().push(quote! {
+            #[doc = #doc_line]
+            pub #ident: #ty
+        });
+        defaults.entry(scope).or_default().push(quote!(#ident: #default));
+    }
+
+    for ef in &input.enum_fields {
+        if schema.field(&ef.field.to_string()).is_none() {
+            errors.push(syn::Error::new(
+                ef.field.span(),
+                format!("enum_field `{}` is not declared in {}", ef.field, rel),
+            ));
+        }
+    }
+
+    let mut errors = errors.into_iter();
+    if let Some(first) = errors.next() {
+        return Err(errors.fold(first, |mut acc, e| {
+            acc.combine(e);
+            acc
+        }));
+    }
+
+    let derives = &input.derives;
+    let structs = input.structs.iter().map(|(scope, name)| {
+        let fields = by_scope.get(scope).cloned().unwrap_or_default();
+        let inits = defaults.get(scope).cloned().unwrap_or_default();
+        let doc_line = format!(" `{}`-scope fields of `{}`.", scope, rel);
+        quote! {
+            #[doc = #doc_line]
+            #[derive(Debug, Clone, PartialEq #(, #derives)*)]
+            pub struct #name {
+                #(#fields,)*
+            }
+
+            impl #name {
+                /// Values from the shard's value block, validated at compile time.
+                pub const DEFAULT: Self = Self {
+                    #(#inits,)*
+                };
+            }
+
+            impl ::core::default::Default for #name {
+                fn default() -> Self {
+                    Self::DEFAULT
+                }
+            }
+        }
+    });
+
+    let module = &input.module;
+    let abs = path.to_string_lossy().to_string();
+    let filename = doc.header.filename.clone().unwrap_or(rel.clone());
+    let rollup = match &doc.rollup {
+        Some(r) => {
+            let hex = format!("0x{}", r.hex);
+            quote!(::core::option::Option::Some(#hex))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+
+    Ok(quote! {
+        pub mod #module {
+            // Rebuild whenever the shard changes.
+            const _SHARD_SOURCE: &str = include_str!(#abs);
+
+            /// `filename` header of the bound shard.
+            pub const SHARD_FILENAME: &str = #filename;
+            /// `hex-rollup` trailer of the bound shard, if any.
+            pub const SHARD_HEX_ROLLUP: ::core::option::Option<&str> = #rollup;
+
+            #(#structs)*
+        }
+    })
+}
+
+fn enum_value(ef: &EnumField, v: &AlnValue) -> Option<TokenStream2> {
+    let ty = &ef.ty;
+    if let Some((_, variant)) = ef.arms.iter().find(|(raw, _)| raw.value() == v.raw) {
+        return Some(quote!(#ty::#variant));
+    }
+    ef.fallback.as_ref().map(|variant| {
+        let raw = v.raw.as_str();
+        quote!(#ty::#variant(#raw))
+    })
+}
+
+fn rust_type(t: AlnDatatype) -> TokenStream2 {
+    match t {
+        AlnDatatype::String => quote!(&'static str),
+        AlnDatatype::Bool => quote!(bool),
+        AlnDatatype::Float => quote!(f32),
+        AlnDatatype::Uint => quote!(u64),
+        AlnDatatype::Int => quote!(i64),
+    }
+}
+
+fn literal(t: AlnDatatype, v: &AlnValue) -> TokenStream2 {
+    match t {
+        AlnDatatype::String => {
+            let s = v.raw.as_str();
+            quote!(#s)
+        }
+        AlnDatatype::Bool => {
+            let b = v.as_bool().unwrap_or(false);
+            quote!(#b)
+        }
+        AlnDatatype::Float => {
+            let lit = Literal::f32_suffixed(v.as_f64().unwrap_or(0.0) as f32);
+            quote!(#lit)
+        }
+        AlnDatatype::Uint => {
+            let lit = Literal::u64_suffixed(v.as_u64().unwrap_or(0));
+            quote!(#lit)
+        }
+        AlnDatatype::Int => {
+            let lit = Literal::i64_suffixed(v.as_i64().unwrap_or(0));
+            quote!(#lit)
+        }
+    }
+}
+
@@ -0,0 +1,10 @@
+use aln_bind::alnbind;
+
+alnbind! {
+    shard "crates/aln_bind/tests/ui/lifeforce_level_out_of_range.aln",
+    module lifeforce,
+    profile_struct LifeforceProfile,
+    policy_struct LifeforcePolicy,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use aln_bind::alnbind;
+
+alnbind! {
+    shard "crates/aln_bind/tests/ui/lifeforce_ok.aln",
+    module lifeforce,
+    profile_struct LifeforceProfile,
+    policy_struct LifeforcePolicy,
+    derive(PartialOrd),
+}
+
+fn main() {
+    let profile = lifeforce::LifeforceProfile::DEFAULT;
+    assert!(profile.partial_cmp(&profile.clone()).is_some());
+}
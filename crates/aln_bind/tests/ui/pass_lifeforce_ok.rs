@@ -0,0 +1,13 @@
+use aln_bind::alnbind;
+
+alnbind! {
+    shard "crates/aln_bind/tests/ui/lifeforce_ok.aln",
+    module lifeforce,
+    profile_struct LifeforceProfile,
+    policy_struct LifeforcePolicy,
+}
+
+fn main() {
+    assert_eq!(lifeforce::LifeforceProfile::DEFAULT.lifeforce_level, 0.82);
+    assert!(!lifeforce::LifeforcePolicy::DEFAULT.nanodebit_merchantpayment_allowed);
+}
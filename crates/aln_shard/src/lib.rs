@@ -0,0 +1,977 @@
+//! Parser for `.aln` qpudatashard files, shared by the wallet loaders and the
+//! `alnbind!` proc-macro.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Column layout of the field/datatype schema block inside a qpudatashard.
+pub const SCHEMA_COLUMNS: [&str; 5] = ["field", "datatype", "description", "required", "scope"];
+
+/// Optional constraint columns a schema block may add after `scope`.
+pub const CONSTRAINT_COLUMNS: [&str; 3] = ["min", "max", "equals"];
+
+/// Column layout of a field/value block inside a qpudatashard.
+pub const VALUE_COLUMNS: [&str; 2] = ["field", "value"];
+
+/// Parsed `.aln` qpudatashard.
+///
+/// Layout understood by the parser:
+/// - optional `aln` opener (may carry `filename` on the same line)
+/// - `filename` / `destination-path` header lines
+/// - `csv` ... `endcsv` blocks (manifest tables, one schema block, value blocks)
+/// - optional `hex-rollup 0x...` trailer and `endaln` closer
+#[derive(Debug, Clone, Default)]
+pub struct AlnDocument {
+    pub header: AlnHeader,
+    /// Generic tables (e.g. the vnode manifest row).
+    pub tables: Vec<AlnTable>,
+    /// Field/datatype/required/scope block, if present.
+    pub schema: Option<AlnSchema>,
+    /// Field/value blocks, in file order.
+    pub values: Vec<AlnValueBlock>,
+    pub rollup: Option<HexRollup>,
+}
+
+/// `filename` / `destination-path` header of a shard.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlnHeader {
+    pub filename: Option<String>,
+    pub destination_path: Option<String>,
+}
+
+/// Any csv block that is neither a schema nor a value block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlnTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<AlnRow>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlnRow {
+    pub cells: Vec<String>,
+    pub line: usize,
+}
+
+/// ALN datatypes used by qpudatashards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlnDatatype {
+    String,
+    Bool,
+    Float,
+    Uint,
+    Int,
+}
+
+impl AlnDatatype {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "string" => Some(AlnDatatype::String),
+            "bool" => Some(AlnDatatype::Bool),
+            "float" => Some(AlnDatatype::Float),
+            "uint" => Some(AlnDatatype::Uint),
+            "int" => Some(AlnDatatype::Int),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlnDatatype::String => "string",
+            AlnDatatype::Bool => "bool",
+            AlnDatatype::Float => "float",
+            AlnDatatype::Uint => "uint",
+            AlnDatatype::Int => "int",
+        }
+    }
+}
+
+/// Scope column of a schema row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlnScope {
+    Profile,
+    Rights,
+    Safety,
+    Policy,
+    State,
+    Metrics,
+    Episode,
+    Other(String),
+}
+
+impl AlnScope {
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "profile" => AlnScope::Profile,
+            "rights" => AlnScope::Rights,
+            "safety" => AlnScope::Safety,
+            "policy" => AlnScope::Policy,
+            "state" => AlnScope::State,
+            "metrics" => AlnScope::Metrics,
+            "episode" => AlnScope::Episode,
+            other => AlnScope::Other(other.to_string()),
+        }
+    }
+}
+
+/// One row of the schema block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlnFieldSpec {
+    pub name: String,
+    pub datatype: AlnDatatype,
+    pub description: String,
+    pub required: bool,
+    pub scope: AlnScope,
+    pub constraint: AlnConstraint,
+    pub line: usize,
+}
+
+/// `min` / `max` / `equals` cells of a schema row; empty cells leave the bound open.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlnConstraint {
+    /// Inclusive lower bound, numeric datatypes only.
+    pub min: Option<f64>,
+    /// Inclusive upper bound, numeric datatypes only.
+    pub max: Option<f64>,
+    /// Raw text the value must equal, e.g. `false` for a fixed invariant.
+    pub equals: Option<String>,
+}
+
+impl AlnConstraint {
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none() && self.equals.is_none()
+    }
+
+    /// Why `value` breaks this constraint, if it does. `value` must already
+    /// have passed `AlnValue::check` for the field's datatype.
+    pub fn violation(&self, value: &AlnValue) -> Option<String> {
+        if let Some(expected) = &self.equals {
+            if value.raw != *expected {
+                return Some(format!("`{}` = {} but must be {}", value.field, value.raw, expected));
+            }
+        }
+        if self.min.is_none() && self.max.is_none() {
+            return None;
+        }
+        let x = value.as_f64().ok()?;
+        let below = self.min.is_some_and(|min| x < min);
+        let above = self.max.is_some_and(|max| x > max);
+        (below || above).then(|| {
+            let bound = |b: Option<f64>| b.map_or_else(|| "..".to_string(), |v| v.to_string());
+            format!(
+                "`{}` = {} is outside [{}, {}]",
+                value.field,
+                value.raw,
+                bound(self.min),
+                bound(self.max)
+            )
+        })
+    }
+}
+
+/// The field/datatype/description/required/scope block, optionally followed by
+/// the `min,max,equals` constraint columns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlnSchema {
+    pub fields: Vec<AlnFieldSpec>,
+    pub line: usize,
+}
+
+impl AlnSchema {
+    pub fn field(&self, name: &str) -> Option<&AlnFieldSpec> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn required_fields(&self) -> impl Iterator<Item = &AlnFieldSpec> {
+        self.fields.iter().filter(|f| f.required)
+    }
+}
+
+/// A single `field,value` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlnValue {
+    pub field: String,
+    /// Unquoted raw value text.
+    pub raw: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl AlnValue {
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn as_bool(&self) -> Result<bool, AlnValueError> {
+        match self.raw.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(self.type_error(AlnDatatype::Bool)),
+        }
+    }
+
+    pub fn as_f64(&self) -> Result<f64, AlnValueError> {
+        self.raw
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| self.type_error(AlnDatatype::Float))
+    }
+
+    pub fn as_u64(&self) -> Result<u64, AlnValueError> {
+        self.raw
+            .parse::<u64>()
+            .map_err(|_| self.type_error(AlnDatatype::Uint))
+    }
+
+    pub fn as_i64(&self) -> Result<i64, AlnValueError> {
+        self.raw
+            .parse::<i64>()
+            .map_err(|_| self.type_error(AlnDatatype::Int))
+    }
+
+    /// Check the raw text against a declared ALN datatype.
+    pub fn check(&self, datatype: AlnDatatype) -> Result<(), AlnValueError> {
+        match datatype {
+            AlnDatatype::String => Ok(()),
+            AlnDatatype::Bool => self.as_bool().map(|_| ()),
+            AlnDatatype::Float => self.as_f64().map(|_| ()),
+            AlnDatatype::Uint => self.as_u64().map(|_| ()),
+            AlnDatatype::Int => self.as_i64().map(|_| ()),
+        }
+    }
+
+    fn type_error(&self, expected: AlnDatatype) -> AlnValueError {
+        AlnValueError {
+            field: self.field.clone(),
+            raw: self.raw.clone(),
+            expected,
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+/// Typed access to a value failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlnValueError {
+    pub field: String,
+    pub raw: String,
+    pub expected: AlnDatatype,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for AlnValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: field `{}` value `{}` is not a valid {}",
+            self.line,
+            self.column,
+            self.field,
+            self.raw,
+            self.expected.as_str()
+        )
+    }
+}
+
+impl std::error::Error for AlnValueError {}
+
+/// A `field,value` block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlnValueBlock {
+    pub entries: Vec<AlnValue>,
+    pub line: usize,
+}
+
+impl AlnValueBlock {
+    pub fn get(&self, field: &str) -> Option<&AlnValue> {
+        self.entries.iter().find(|v| v.field == field)
+    }
+}
+
+/// `hex-rollup` trailer (stored without the `0x` prefix).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexRollup {
+    pub hex: String,
+    pub line: usize,
+}
+
+impl HexRollup {
+    pub fn bytes(&self) -> Vec<u8> {
+        self.hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let s = std::str::from_utf8(pair).unwrap_or("00");
+                u8::from_str_radix(s, 16).unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Rollups are usually hex-encoded ASCII summaries of the shard.
+    pub fn as_text(&self) -> Option<String> {
+        String::from_utf8(self.bytes()).ok()
+    }
+}
+
+/// Parse failure with 1-based line/column position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlnParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: AlnParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlnParseErrorKind {
+    UnexpectedDirective(String),
+    MissingDirectiveValue(&'static str),
+    DuplicateHeader(&'static str),
+    UnterminatedBlock,
+    UnterminatedQuote,
+    EmptyBlock,
+    ColumnCountMismatch { expected: usize, found: usize },
+    UnknownDatatype(String),
+    InvalidRequiredFlag(String),
+    /// A constraint cell does not fit the field's datatype.
+    InvalidConstraint { column: &'static str, raw: String },
+    DuplicateField(String),
+    DuplicateSchema,
+    InvalidHexRollup,
+    ContentAfterEnd,
+}
+
+impl fmt::Display for AlnParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            AlnParseErrorKind::UnexpectedDirective(d) => write!(f, "unexpected directive `{}`", d),
+            AlnParseErrorKind::MissingDirectiveValue(d) => write!(f, "`{}` requires a value", d),
+            AlnParseErrorKind::DuplicateHeader(d) => write!(f, "duplicate `{}` header", d),
+            AlnParseErrorKind::UnterminatedBlock => write!(f, "`csv` block is missing `endcsv`"),
+            AlnParseErrorKind::UnterminatedQuote => write!(f, "unterminated quoted cell"),
+            AlnParseErrorKind::EmptyBlock => write!(f, "`csv` block has no column header"),
+            AlnParseErrorKind::ColumnCountMismatch { expected, found } => {
+                write!(f, "expected {} columns, found {}", expected, found)
+            }
+            AlnParseErrorKind::UnknownDatatype(t) => write!(f, "unknown datatype `{}`", t),
+            AlnParseErrorKind::InvalidRequiredFlag(v) => {
+                write!(f, "`required` must be true or false, found `{}`", v)
+            }
+            AlnParseErrorKind::InvalidConstraint { column, raw } => {
+                write!(f, "`{}` constraint `{}` does not fit the field's datatype", column, raw)
+            }
+            AlnParseErrorKind::DuplicateField(n) => write!(f, "field `{}` declared twice", n),
+            AlnParseErrorKind::DuplicateSchema => write!(f, "shard has more than one schema block"),
+            AlnParseErrorKind::InvalidHexRollup => write!(f, "hex-rollup is not valid 0x-prefixed hex"),
+            AlnParseErrorKind::ContentAfterEnd => write!(f, "content after `endaln`"),
+        }
+    }
+}
+
+impl std::error::Error for AlnParseError {}
+
+/// Failure loading a shard from disk.
+#[derive(Debug)]
+pub enum AlnLoadError {
+    Io(io::Error),
+    Parse(AlnParseError),
+}
+
+impl fmt::Display for AlnLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlnLoadError::Io(e) => write!(f, "failed to read shard: {}", e),
+            AlnLoadError::Parse(e) => write!(f, "failed to parse shard: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AlnLoadError {}
+
+impl From<io::Error> for AlnLoadError {
+    fn from(e: io::Error) -> Self {
+        AlnLoadError::Io(e)
+    }
+}
+
+impl From<AlnParseError> for AlnLoadError {
+    fn from(e: AlnParseError) -> Self {
+        AlnLoadError::Parse(e)
+    }
+}
+
+/// Cell split out of a csv row, with the 1-based column it started at.
+struct Cell {
+    text: String,
+    column: usize,
+}
+
+impl AlnDocument {
+    /// Read and parse a shard file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, AlnLoadError> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::parse(&text)?)
+    }
+
+    /// Parse shard text into a typed document.
+    pub fn parse(text: &str) -> Result<Self, AlnParseError> {
+        let mut doc = AlnDocument::default();
+        let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l));
+        let mut ended = false;
+
+        while let Some((line_no, line)) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let indent = char_len(line) - char_len(line.trim_start());
+            if ended {
+                return Err(err(line_no, indent + 1, AlnParseErrorKind::ContentAfterEnd));
+            }
+
+            let (directive, rest) = split_directive(trimmed);
+            let rest_col = indent + 1 + (char_len(trimmed) - char_len(rest));
+            match directive {
+                "aln" => {
+                    // `aln filename <path>` is accepted on a single line.
+                    if !rest.is_empty() {
+                        let (inner, value) = split_directive(rest);
+                        if inner != "filename" {
+                            return Err(err(
+                                line_no,
+                                rest_col,
+                                AlnParseErrorKind::UnexpectedDirective(inner.to_string()),
+                            ));
+                        }
+                        let value_col = rest_col + (char_len(rest) - char_len(value));
+                        set_header(&mut doc.header.filename, "filename", value, line_no, value_col)?;
+                    }
+                }
+                "filename" => {
+                    set_header(&mut doc.header.filename, "filename", rest, line_no, rest_col)?;
+                }
+                "destination-path" => {
+                    set_header(
+                        &mut doc.header.destination_path,
+                        "destination-path",
+                        rest,
+                        line_no,
+                        rest_col,
+                    )?;
+                }
+                "csv" => {
+                    parse_csv_block(&mut doc, line_no, &mut lines)?;
+                }
+                "hex-rollup" => {
+                    let hex = rest.strip_prefix("0x").unwrap_or("");
+                    if hex.is_empty()
+                        || hex.len() % 2 != 0
+                        || !hex.chars().all(|c| c.is_ascii_hexdigit())
+                    {
+                        return Err(err(line_no, rest_col, AlnParseErrorKind::InvalidHexRollup));
+                    }
+                    if doc.rollup.is_some() {
+                        return Err(err(
+                            line_no,
+                            indent + 1,
+                            AlnParseErrorKind::DuplicateHeader("hex-rollup"),
+                        ));
+                    }
+                    doc.rollup = Some(HexRollup {
+                        hex: hex.to_string(),
+                        line: line_no,
+                    });
+                }
+                "endaln" => ended = true,
+                other => {
+                    return Err(err(
+                        line_no,
+                        indent + 1,
+                        AlnParseErrorKind::UnexpectedDirective(other.to_string()),
+                    ));
+                }
+            }
+        }
+
+        Ok(doc)
+    }
+
+    /// Schema entry for `name`, if the shard declares it.
+    pub fn field_spec(&self, name: &str) -> Option<&AlnFieldSpec> {
+        self.schema.as_ref().and_then(|s| s.field(name))
+    }
+
+    /// Last value assigned to `name` across all value blocks.
+    pub fn value(&self, name: &str) -> Option<&AlnValue> {
+        self.values.iter().rev().find_map(|b| b.get(name))
+    }
+}
+
+fn err(line: usize, column: usize, kind: AlnParseErrorKind) -> AlnParseError {
+    AlnParseError { line, column, kind }
+}
+
+/// Length in chars; columns are counted in chars, not bytes.
+fn char_len(s: &str) -> usize {
+    s.chars().count()
+}
+
+fn split_directive(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim_start()),
+        None => (s, ""),
+    }
+}
+
+fn set_header(
+    slot: &mut Option<String>,
+    name: &'static str,
+    value: &str,
+    line: usize,
+    column: usize,
+) -> Result<(), AlnParseError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(err(line, column, AlnParseErrorKind::MissingDirectiveValue(name)));
+    }
+    if slot.is_some() {
+        return Err(err(line, column, AlnParseErrorKind::DuplicateHeader(name)));
+    }
+    *slot = Some(value.to_string());
+    Ok(())
+}
+
+/// Comment lines inside csv blocks look like `-- text --`.
+fn is_comment(trimmed: &str) -> bool {
+    trimmed.starts_with("--")
+}
+
+fn parse_csv_block<'t, I>(doc: &mut AlnDocument, opened_at: usize, lines: &mut I) -> Result<(), AlnParseError>
+where
+    I: Iterator<Item = (usize, &'t str)>,
+{
+    let mut columns: Option<(Vec<String>, usize)> = None;
+    let mut rows: Vec<(Vec<Cell>, usize)> = Vec::new();
+
+    loop {
+        let (line_no, line) = match lines.next() {
+            Some(l) => l,
+            None => return Err(err(opened_at, 1, AlnParseErrorKind::UnterminatedBlock)),
+        };
+        let trimmed = line.trim();
+        if trimmed.is_empty() || is_comment(trimmed) {
+            continue;
+        }
+        if trimmed == "endcsv" {
+            break;
+        }
+
+        let cells = split_row(line, line_no)?;
+        match &columns {
+            None => {
+                let names = cells.into_iter().map(|c| c.text).collect();
+                columns = Some((names, line_no));
+            }
+            Some((names, _)) => {
+                if cells.len() != names.len() {
+                    let column = cells
+                        .get(names.len())
+                        .map(|c| c.column)
+                        .unwrap_or(char_len(line.trim_end()) + 1);
+                    return Err(err(
+                        line_no,
+                        column,
+                        AlnParseErrorKind::ColumnCountMismatch {
+                            expected: names.len(),
+                            found: cells.len(),
+                        },
+                    ));
+                }
+                rows.push((cells, line_no));
+            }
+        }
+    }
+
+    let (names, header_line) = match columns {
+        Some(c) => c,
+        None => return Err(err(opened_at, 1, AlnParseErrorKind::EmptyBlock)),
+    };
+
+    let is_schema = names.len() >= SCHEMA_COLUMNS.len()
+        && names[..SCHEMA_COLUMNS.len()] == SCHEMA_COLUMNS
+        && (names.len() == SCHEMA_COLUMNS.len() || names[SCHEMA_COLUMNS.len()..] == CONSTRAINT_COLUMNS);
+    if is_schema {
+        if doc.schema.is_some() {
+            return Err(err(header_line, 1, AlnParseErrorKind::DuplicateSchema));
+        }
+        doc.schema = Some(build_schema(rows, header_line)?);
+    } else if names == VALUE_COLUMNS {
+        doc.values.push(build_values(rows, header_line)?);
+    } else {
+        doc.tables.push(AlnTable {
+            columns: names,
+            rows: rows
+                .into_iter()
+                .map(|(cells, line)| AlnRow {
+                    cells: cells.into_iter().map(|c| c.text).collect(),
+                    line,
+                })
+                .collect(),
+            line: header_line,
+        });
+    }
+    Ok(())
+}
+
+fn build_schema(rows: Vec<(Vec<Cell>, usize)>, header_line: usize) -> Result<AlnSchema, AlnParseError> {
+    let mut schema = AlnSchema {
+        fields: Vec::with_capacity(rows.len()),
+        line: header_line,
+    };
+    for (mut cells, line) in rows {
+        let constraint_cells = cells.split_off(SCHEMA_COLUMNS.len());
+        let scope = cells.pop().expect("row width checked");
+        let required = cells.pop().expect("row width checked");
+        let description = cells.pop().expect("row width checked");
+        let datatype = cells.pop().expect("row width checked");
+        let name = cells.pop().expect("row width checked");
+
+        if schema.field(&name.text).is_some() {
+            return Err(err(line, name.column, AlnParseErrorKind::DuplicateField(name.text)));
+        }
+        let datatype = AlnDatatype::parse(&datatype.text).ok_or_else(|| {
+            err(line, datatype.column, AlnParseErrorKind::UnknownDatatype(datatype.text.clone()))
+        })?;
+        let required = match required.text.as_str() {
+            "true" => true,
+            "false" => false,
+            _ => {
+                return Err(err(
+                    line,
+                    required.column,
+                    AlnParseErrorKind::InvalidRequiredFlag(required.text),
+                ))
+            }
+        };
+
+        let constraint = build_constraint(constraint_cells, &name.text, datatype, line)?;
+
+        schema.fields.push(AlnFieldSpec {
+            name: name.text,
+            datatype,
+            description: description.text,
+            required,
+            scope: AlnScope::parse(&scope.text),
+            constraint,
+            line,
+        });
+    }
+    Ok(schema)
+}
+
+/// `min` / `max` must be numbers on a numeric field; `equals` must be a valid
+/// value of the field's datatype.
+fn build_constraint(
+    cells: Vec<Cell>,
+    field: &str,
+    datatype: AlnDatatype,
+    line: usize,
+) -> Result<AlnConstraint, AlnParseError> {
+    let mut constraint = AlnConstraint::default();
+    let numeric = matches!(datatype, AlnDatatype::Float | AlnDatatype::Uint | AlnDatatype::Int);
+    for (column, cell) in CONSTRAINT_COLUMNS.into_iter().zip(cells) {
+        if cell.text.is_empty() {
+            continue;
+        }
+        let invalid = || {
+            err(
+                line,
+                cell.column,
+                AlnParseErrorKind::InvalidConstraint {
+                    column,
+                    raw: cell.text.clone(),
+                },
+            )
+        };
+        let as_value = AlnValue {
+            field: field.to_string(),
+            raw: cell.text.clone(),
+            line,
+            column: cell.column,
+        };
+        match column {
+            "equals" => {
+                as_value.check(datatype).map_err(|_| invalid())?;
+                constraint.equals = Some(cell.text.clone());
+            }
+            bound => {
+                let v = as_value.as_f64().ok().filter(|_| numeric).ok_or_else(invalid)?;
+                if bound == "min" {
+                    constraint.min = Some(v);
+                } else {
+                    constraint.max = Some(v);
+                }
+            }
+        }
+    }
+    Ok(constraint)
+}
+
+fn build_values(rows: Vec<(Vec<Cell>, usize)>, header_line: usize) -> Result<AlnValueBlock, AlnParseError> {
+    let mut block = AlnValueBlock {
+        entries: Vec::with_capacity(rows.len()),
+        line: header_line,
+    };
+    for (mut cells, line) in rows {
+        let value = cells.pop().expect("row width checked");
+        let field = cells.pop().expect("row width checked");
+        if block.get(&field.text).is_some() {
+            return Err(err(line, field.column, AlnParseErrorKind::DuplicateField(field.text)));
+        }
+        block.entries.push(AlnValue {
+            field: field.text,
+            raw: value.text,
+            line,
+            column: value.column,
+        });
+    }
+    Ok(block)
+}
+
+/// Split one csv row, honouring `"quoted, cells"` and `""` escapes.
+fn split_row(line: &str, line_no: usize) -> Result<Vec<Cell>, AlnParseError> {
+    let mut cells = Vec::new();
+    let mut chars = line.chars().peekable();
+    let mut current = String::new();
+    let mut start_col = 1;
+    let mut in_quotes = false;
+    let mut quote_col = 0;
+    let mut col = 0;
+
+    while let Some(c) = chars.next() {
+        col += 1;
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    col += 1;
+                    current.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                current.push(c);
+            }
+            continue;
+        }
+        match c {
+            '"' if current.trim().is_empty() => {
+                current.clear();
+                in_quotes = true;
+                quote_col = col;
+            }
+            ',' => {
+                cells.push(Cell {
+                    text: current.trim().to_string(),
+                    column: start_col,
+                });
+                current.clear();
+                start_col = col + 1;
+            }
+            _ => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(err(line_no, quote_col, AlnParseErrorKind::UnterminatedQuote));
+    }
+    cells.push(Cell {
+        text: current.trim().to_string(),
+        column: start_col,
+    });
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_err(text: &str) -> AlnParseError {
+        AlnDocument::parse(text).expect_err("shard should be rejected")
+    }
+
+    #[test]
+    fn parses_header_schema_values_and_rollup() {
+        let doc = AlnDocument::parse(
+            "aln filename shards/w.aln\n\
+             destination-path /wallet\n\
+             csv\n\
+             field,datatype,description,required,scope\n\
+             -- profile --\n\
+             walletdid,string,Wallet DID,true,profile\n\
+             maxauto,uint,Auto limit,false,policy\n\
+             endcsv\n\
+             csv\n\
+             field,value\n\
+             walletdid,did:aln:1\n\
+             maxauto,5000\n\
+             endcsv\n\
+             hex-rollup 0x4F4B\n\
+             endaln\n",
+        )
+        .unwrap();
+
+        assert_eq!(doc.header.filename.as_deref(), Some("shards/w.aln"));
+        assert_eq!(doc.header.destination_path.as_deref(), Some("/wallet"));
+        let spec = doc.field_spec("maxauto").unwrap();
+        assert_eq!(spec.datatype, AlnDatatype::Uint);
+        assert!(!spec.required);
+        assert_eq!(spec.scope, AlnScope::Policy);
+        assert_eq!(doc.value("maxauto").unwrap().as_u64(), Ok(5000));
+        let rollup = doc.rollup.as_ref().unwrap();
+        assert_eq!(rollup.line, 14);
+        assert_eq!(rollup.as_text().as_deref(), Some("OK"));
+    }
+
+    #[test]
+    fn quoted_cells_keep_commas_and_escaped_quotes() {
+        let doc = AlnDocument::parse(
+            "csv\n\
+             field,value\n\
+             note,\"a, \"\"quoted\"\" b\"\n\
+             plain,  spaced  \n\
+             endcsv\n",
+        )
+        .unwrap();
+
+        assert_eq!(doc.value("note").unwrap().as_str(), "a, \"quoted\" b");
+        assert_eq!(doc.value("plain").unwrap().as_str(), "spaced");
+    }
+
+    #[test]
+    fn unterminated_quote_points_at_the_opening_quote() {
+        let e = parse_err("csv\nfield,value\nnote,\"open\nendcsv\n");
+        assert_eq!((e.line, e.column), (3, 6));
+        assert_eq!(e.kind, AlnParseErrorKind::UnterminatedQuote);
+    }
+
+    #[test]
+    fn column_mismatch_points_at_the_extra_cell() {
+        let e = parse_err("csv\nfield,value\na,1,extra\nendcsv\n");
+        assert_eq!((e.line, e.column), (3, 5));
+        assert_eq!(
+            e.kind,
+            AlnParseErrorKind::ColumnCountMismatch {
+                expected: 2,
+                found: 3
+            }
+        );
+    }
+
+    #[test]
+    fn columns_count_chars_on_non_ascii_lines() {
+        // `é` and `ü` are two bytes each; columns must not drift.
+        let e = parse_err("csv\nfield,value\ncafé,ü,x\nendcsv\n");
+        assert_eq!((e.line, e.column), (3, 8));
+
+        let e = parse_err("\u{a0}\u{a0}bogus\n");
+        assert_eq!((e.line, e.column), (1, 3));
+        assert_eq!(e.kind, AlnParseErrorKind::UnexpectedDirective("bogus".into()));
+    }
+
+    #[test]
+    fn schema_errors_carry_cell_positions() {
+        let e = parse_err(
+            "csv\nfield,datatype,description,required,scope\nx,decimal,d,true,policy\nendcsv\n",
+        );
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.kind, AlnParseErrorKind::UnknownDatatype("decimal".into()));
+
+        let e = parse_err(
+            "csv\nfield,datatype,description,required,scope\nx,bool,d,yes,policy\nendcsv\n",
+        );
+        assert_eq!((e.line, e.column), (3, 10));
+    }
+
+    #[test]
+    fn constraint_columns_are_parsed_and_checked() {
+        let doc = AlnDocument::parse(
+            "csv\n\
+             field,datatype,description,required,scope,min,max,equals\n\
+             level,float,any wording,true,profile,0.0,1.0,\n\
+             forbidden,bool,any wording,true,policy,,,false\n\
+             free,uint,no constraint,true,policy,,,\n\
+             endcsv\n\
+             csv\n\
+             field,value\n\
+             level,1.4\n\
+             forbidden,true\n\
+             free,7\n\
+             endcsv\n",
+        )
+        .unwrap();
+
+        let level = doc.field_spec("level").unwrap();
+        assert_eq!((level.constraint.min, level.constraint.max), (Some(0.0), Some(1.0)));
+        assert_eq!(
+            level.constraint.violation(doc.value("level").unwrap()).as_deref(),
+            Some("`level` = 1.4 is outside [0, 1]")
+        );
+        let forbidden = doc.field_spec("forbidden").unwrap();
+        assert_eq!(forbidden.constraint.equals.as_deref(), Some("false"));
+        assert!(forbidden.constraint.violation(doc.value("forbidden").unwrap()).is_some());
+        let free = doc.field_spec("free").unwrap();
+        assert!(free.constraint.is_empty());
+        assert!(free.constraint.violation(doc.value("free").unwrap()).is_none());
+    }
+
+    #[test]
+    fn constraints_must_fit_the_datatype() {
+        let e = parse_err(
+            "csv\nfield,datatype,description,required,scope,min,max,equals\nx,bool,d,true,policy,0,,\nendcsv\n",
+        );
+        assert_eq!((e.line, e.column), (3, 22));
+        assert_eq!(
+            e.kind,
+            AlnParseErrorKind::InvalidConstraint {
+                column: "min",
+                raw: "0".into()
+            }
+        );
+
+        let e = parse_err(
+            "csv\nfield,datatype,description,required,scope,min,max,equals\nx,uint,d,true,policy,,,maybe\nendcsv\n",
+        );
+        assert_eq!(e.kind, AlnParseErrorKind::InvalidConstraint { column: "equals", raw: "maybe".into() });
+    }
+
+    #[test]
+    fn block_errors() {
+        let e = parse_err("csv\nfield,value\n");
+        assert_eq!((e.line, e.kind), (1, AlnParseErrorKind::UnterminatedBlock));
+
+        let e = parse_err("csv\nendcsv\n");
+        assert_eq!((e.line, e.kind), (1, AlnParseErrorKind::EmptyBlock));
+
+        let e = parse_err("endaln\ncsv\n");
+        assert_eq!((e.line, e.column, e.kind), (2, 1, AlnParseErrorKind::ContentAfterEnd));
+    }
+
+    #[test]
+    fn invalid_hex_rollups_are_rejected() {
+        for bad in ["hex-rollup 0x", "hex-rollup 0xABC", "hex-rollup 0xZZ", "hex-rollup 4F4B"] {
+            let e = parse_err(bad);
+            assert_eq!((e.line, e.column), (1, 12), "{}", bad);
+            assert_eq!(e.kind, AlnParseErrorKind::InvalidHexRollup);
+        }
+
+        let e = parse_err("hex-rollup 0x4F\nhex-rollup 0x4B\n");
+        assert_eq!(e.line, 2);
+        assert_eq!(e.kind, AlnParseErrorKind::DuplicateHeader("hex-rollup"));
+    }
+}
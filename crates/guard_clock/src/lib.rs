@@ -0,0 +1,94 @@
+//! Wall-clock source shared by the paycomp and lifeforce guards, so both can be
+//! driven by one `Clock` in replays and tests.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Wall-clock source for guards.
+///
+/// paycomp's `AugFingerprintGuard::evaluate_with_clock`,
+/// `AugCitizenPosGuard::with_clock` and `CyberNanoPaymentGuard::eval_with_clock`,
+/// and lifeforce's `CyberNanoPaymentGuard::eval_with_clock`, read their time from
+/// a `Clock`. Share one `Clock` between them and a run can be replayed or tested
+/// deterministically.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// The host's real clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Always returns the same instant, e.g. when replaying a recorded decision.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// Clock that only moves when told to, for simulations and tests.
+#[derive(Debug)]
+pub struct SimulatedClock {
+    now: Mutex<SystemTime>,
+}
+
+impl SimulatedClock {
+    pub fn new(start: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+
+    pub fn set(&self, to: SystemTime) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = to;
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_790_000_000 + secs)
+    }
+
+    #[test]
+    fn fixed_clock_never_moves() {
+        let clock = FixedClock(at(5));
+        assert_eq!(clock.now(), at(5));
+        assert_eq!(clock.now(), at(5));
+    }
+
+    #[test]
+    fn simulated_clock_moves_only_when_told() {
+        let clock = SimulatedClock::new(at(0));
+        assert_eq!(clock.now(), at(0));
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now(), at(90));
+        // `set` may also step back, as a host clock correction would.
+        clock.set(at(30));
+        assert_eq!(clock.now(), at(30));
+    }
+}
@@ -1,13 +1,24 @@
 use aln_bind::alnbind;
 
+pub mod cybernano_payment_guard;
+pub mod lifeforce_band;
+
 // This tells the codegen to:
 // - parse the ALN shard
-// - generate typed structs + enums
+// - generate typed structs + enums (serializable, for decision logs)
 // - enforce basic range constraints at load time.
 alnbind! {
     shard "qpudatashards/au_lifeforce_cybernano_bostrom2026.aln",
     module lifeforce_bostrom2026,
     profile_struct LifeforceProfile,
     policy_struct LifeforcePolicy,
-    state_struct LifeforceState
+    state_struct LifeforceState,
+    metrics_struct LifeforceMetrics,
+    derive(serde::Serialize),
+    enum_field lifeforce_band: crate::lifeforce_band::LifeforceBand {
+        "stable" => Stable,
+        "fragile" => Fragile,
+        "recovering" => Recovering,
+        _ => Other,
+    }
 }
@@ -0,0 +1,9 @@
+/// Lifeforce-chi band as bound from the ALN `lifeforce_band` field.
+/// Unknown spellings are kept in `Other` and treated as fragile by the guard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum LifeforceBand {
+    Stable,
+    Fragile,
+    Recovering,
+    Other(&'static str),
+}
@@ -0,0 +1,264 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::consent_audit_log::sha256_hex;
+use crate::merchant_view::ReasonCategory;
+use crate::paycomp_augfingerprint_guard::{AugFingerprintShard, ConsentAuditRecord, ConsentDecision};
+
+/// How inner-state values are written into `ConsentAuditRecord`.
+///
+/// Anything but `Raw` keeps exact S_t / L_t out of the audit trail, in line with
+/// the `noscorefrominnerstate` right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditRedaction {
+    /// Exact values, for the host's own local diagnostics only.
+    Raw,
+    /// Band labels relative to the entry corridor.
+    Bands,
+    /// Band labels plus salted SHA-256 commitments to the exact values.
+    Commitments,
+}
+
+/// Position of a value relative to its corridor band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandLabel {
+    Below,
+    InCorridor,
+    Above,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InnerStateBands {
+    pub s_band: BandLabel,
+    pub load_band: BandLabel,
+}
+
+/// Inner-state evidence with no raw values in it, by construction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RedactedInnerState {
+    Bands(InnerStateBands),
+    /// `commitment = sha256(salt || wallet_did || timestamp || field || value)`;
+    /// the host can later open it by revealing the salt and value.
+    Commitments {
+        bands: InnerStateBands,
+        s_commitment: String,
+        load_commitment: String,
+    },
+}
+
+impl RedactedInnerState {
+    pub fn bands(&self) -> InnerStateBands {
+        match self {
+            RedactedInnerState::Bands(b) => *b,
+            RedactedInnerState::Commitments { bands, .. } => *bands,
+        }
+    }
+}
+
+/// Inner-state evidence stored in a `ConsentAuditRecord`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "storage", rename_all = "snake_case")]
+pub enum InnerStateRecord {
+    Raw {
+        s_value: f32,
+        load_value: f32,
+        bands: InnerStateBands,
+    },
+    Redacted(RedactedInnerState),
+}
+
+impl InnerStateRecord {
+    /// Drop raw values, keeping only the band labels.
+    pub fn redacted(&self) -> RedactedInnerState {
+        match self {
+            InnerStateRecord::Raw { bands, .. } => RedactedInnerState::Bands(*bands),
+            InnerStateRecord::Redacted(r) => r.clone(),
+        }
+    }
+}
+
+fn commit(salt: &[u8], wallet_did: &str, at: SystemTime, field: &str, value: f32) -> String {
+    let nanos = at.duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    let mut bytes = Vec::with_capacity(salt.len() + wallet_did.len() + 32);
+    bytes.extend_from_slice(salt);
+    bytes.extend_from_slice(wallet_did.as_bytes());
+    bytes.extend_from_slice(&nanos.to_be_bytes());
+    bytes.extend_from_slice(field.as_bytes());
+    bytes.extend_from_slice(&value.to_bits().to_be_bytes());
+    sha256_hex(&bytes)
+}
+
+impl AugFingerprintShard {
+    /// Band labels for the current S_t / L_t against the entry corridor.
+    pub fn inner_state_bands(&self) -> InnerStateBands {
+        let ns = self.neuro_state;
+        let entry = self.corridor().entry;
+        let s_band = if ns.svalue < entry.smin {
+            BandLabel::Below
+        } else if ns.svalue > entry.smax {
+            BandLabel::Above
+        } else {
+            BandLabel::InCorridor
+        };
+        let load_band = if ns.loadvalue > entry.loadmax {
+            BandLabel::Above
+        } else {
+            BandLabel::InCorridor
+        };
+        InnerStateBands { s_band, load_band }
+    }
+
+    /// Inner-state evidence for an audit record at `at`, per `audit_redaction`.
+    /// `Commitments` without an `audit_salt` falls back to plain bands.
+    pub fn audit_inner_state(&self, at: SystemTime) -> InnerStateRecord {
+        let bands = self.inner_state_bands();
+        let ns = self.neuro_state;
+        match (self.audit_redaction, self.audit_salt.as_deref()) {
+            (AuditRedaction::Raw, _) => InnerStateRecord::Raw {
+                s_value: ns.svalue,
+                load_value: ns.loadvalue,
+                bands,
+            },
+            (AuditRedaction::Commitments, Some(salt)) => {
+                InnerStateRecord::Redacted(RedactedInnerState::Commitments {
+                    bands,
+                    s_commitment: commit(salt, &self.wallet_did, at, "s", ns.svalue),
+                    load_commitment: commit(salt, &self.wallet_did, at, "load", ns.loadvalue),
+                })
+            }
+            (AuditRedaction::Bands, _) | (AuditRedaction::Commitments, None) => {
+                InnerStateRecord::Redacted(RedactedInnerState::Bands(bands))
+            }
+        }
+    }
+}
+
+/// Merchant-facing export of a consent record.
+///
+/// Holds nothing about the host's inner state: no S_t / L_t evidence, not even
+/// bands, no companion macro-state and only the coarse `ReasonCategory`. The
+/// wallet DID is replaced by a per-merchant pseudonym, so a merchant can link
+/// its own records for one wallet but not match them with another merchant's.
+/// `decision_id` lets a dispute be settled against the host's audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerchantAuditRecord {
+    pub decision_id: Uuid,
+    /// `sha256(wallet_did || 0x00 || merchant_id)`, lower-case hex.
+    pub wallet_pseudonym: String,
+    pub merchant_id: String,
+    pub amount_mills: u64,
+    pub decision: ConsentDecision,
+    pub category: ReasonCategory,
+    pub timestamp: SystemTime,
+}
+
+/// Per-merchant pseudonym for `wallet_did`.
+pub fn wallet_pseudonym(wallet_did: &str, merchant_id: &str) -> String {
+    let mut bytes = Vec::with_capacity(wallet_did.len() + merchant_id.len() + 1);
+    bytes.extend_from_slice(wallet_did.as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(merchant_id.as_bytes());
+    sha256_hex(&bytes)
+}
+
+impl ConsentAuditRecord {
+    pub fn merchant_export(&self) -> MerchantAuditRecord {
+        MerchantAuditRecord {
+            decision_id: self.decision_id,
+            wallet_pseudonym: wallet_pseudonym(&self.wallet_did, &self.merchant_id),
+            merchant_id: self.merchant_id.clone(),
+            amount_mills: self.amount_mills,
+            decision: self.decision,
+            category: ReasonCategory::from_reason(self.reason),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::{
+        AugFingerprintGuard, ConsentReason, PaymentRequest,
+    };
+    use crate::test_support::{at, pay, sample};
+
+    fn shard(redaction: AuditRedaction, svalue: f32) -> AugFingerprintShard {
+        let mut shard = crate::test_support::shard();
+        shard.audit_redaction = redaction;
+        shard.update_neuro_state(sample(svalue, 0));
+        shard
+    }
+
+    fn record(shard: &mut AugFingerprintShard, merchant_id: &str) -> ConsentAuditRecord {
+        let request = PaymentRequest {
+            merchant_id: merchant_id.into(),
+            ..pay(1_000, false, 0)
+        };
+        AugFingerprintGuard::evaluate(shard, &request, None).2.unwrap()
+    }
+
+    #[test]
+    fn redaction_mode_controls_the_stored_inner_state() {
+        let raw = shard(AuditRedaction::Raw, 0.9).audit_inner_state(at(0));
+        assert!(matches!(raw, InnerStateRecord::Raw { s_value, .. } if s_value == 0.9));
+        let expected = InnerStateBands {
+            s_band: BandLabel::Above,
+            load_band: BandLabel::InCorridor,
+        };
+        assert_eq!(raw.redacted(), RedactedInnerState::Bands(expected));
+
+        let bands = shard(AuditRedaction::Bands, 0.3).audit_inner_state(at(0));
+        let expected = InnerStateBands {
+            s_band: BandLabel::Below,
+            load_band: BandLabel::InCorridor,
+        };
+        assert_eq!(bands, InnerStateRecord::Redacted(RedactedInnerState::Bands(expected)));
+
+        // Commitments need a salt; without one only bands are kept.
+        let mut committed = shard(AuditRedaction::Commitments, 0.5);
+        assert!(matches!(
+            committed.audit_inner_state(at(0)),
+            InnerStateRecord::Redacted(RedactedInnerState::Bands(_))
+        ));
+        committed.audit_salt = Some(b"salt".to_vec());
+        let first = committed.audit_inner_state(at(0));
+        assert!(matches!(
+            first,
+            InnerStateRecord::Redacted(RedactedInnerState::Commitments { .. })
+        ));
+        assert_eq!(first, committed.audit_inner_state(at(0)));
+        committed.audit_salt = Some(b"other".to_vec());
+        assert_ne!(first, committed.audit_inner_state(at(0)));
+    }
+
+    #[test]
+    fn merchant_export_holds_no_did_inner_state_or_exact_reason() {
+        let mut shard = shard(AuditRedaction::Raw, 0.9);
+        let record = record(&mut shard, "m-1");
+        assert_eq!(record.reason, ConsentReason::StateOutsideCorridor);
+
+        let export = record.merchant_export();
+        assert_eq!(export.decision_id, record.decision_id);
+        assert_eq!(export.category, ReasonCategory::HostNotReady);
+        let json = serde_json::to_string(&export).unwrap();
+        for leak in ["did:aln:host", "0.9", "outside", "s_band", "inner_state", "ai_consent"] {
+            assert!(!json.contains(leak), "{} in {}", leak, json);
+        }
+    }
+
+    #[test]
+    fn wallet_pseudonym_is_stable_per_merchant_only() {
+        let mut shard = shard(AuditRedaction::Bands, 0.5);
+        let a1 = record(&mut shard, "m-1").merchant_export();
+        let a2 = record(&mut shard, "m-1").merchant_export();
+        let b = record(&mut shard, "m-2").merchant_export();
+        assert_eq!(a1.wallet_pseudonym, a2.wallet_pseudonym);
+        assert_ne!(a1.wallet_pseudonym, b.wallet_pseudonym);
+    }
+}
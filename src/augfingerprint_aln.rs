@@ -0,0 +1,682 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use aln_shard::{AlnDocument, AlnValue, AlnValueError};
+
+use crate::audit_redaction::AuditRedaction;
+use crate::augfingerprint_corridor::{
+    AuStatus, AugFingerprintCorridor, InterfacePrimary, PreferredConsentMode,
+};
+use crate::consent_episode::FeedbackLabel;
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentPolicy, AugFingerprintShard, ControlMode, PromptAccounting,
+};
+
+/// A single problem found while hydrating an `AugFingerprintShard` from ALN.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShardLoadError {
+    /// The shard has no field/datatype schema block.
+    MissingSchema,
+    /// The shard has no field/value block.
+    MissingValues,
+    /// Schema marks the field `required=true` but no value is given.
+    MissingRequiredField { field: String },
+    /// Value does not parse as its declared datatype.
+    InvalidValue(AlnValueError),
+    /// String value is not one of the accepted enum spellings.
+    InvalidEnum {
+        field: String,
+        raw: String,
+        expected: &'static str,
+    },
+    /// Numeric value is outside its allowed range.
+    OutOfRange {
+        field: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    /// `neuros_smin` is above `neuros_smax`.
+    CorridorInverted { smin: f32, smax: f32 },
+    /// The latency band's minimum is above its maximum.
+    LatencyBandInverted { min: u64, max: u64 },
+}
+
+impl fmt::Display for ShardLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShardLoadError::MissingSchema => write!(f, "shard has no schema block"),
+            ShardLoadError::MissingValues => write!(f, "shard has no value block"),
+            ShardLoadError::MissingRequiredField { field } => {
+                write!(f, "required field `{}` has no value", field)
+            }
+            ShardLoadError::InvalidValue(e) => write!(f, "{}", e),
+            ShardLoadError::InvalidEnum { field, raw, expected } => {
+                write!(f, "field `{}` value `{}` is not one of: {}", field, raw, expected)
+            }
+            ShardLoadError::OutOfRange { field, value, min, max } => {
+                write!(f, "field `{}` value {} is outside [{}, {}]", field, value, min, max)
+            }
+            ShardLoadError::CorridorInverted { smin, smax } => {
+                write!(f, "neuros_smin {} is above neuros_smax {}", smin, smax)
+            }
+            ShardLoadError::LatencyBandInverted { min, max } => {
+                write!(f, "latency band minimum {} ms is above its maximum {} ms", min, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShardLoadError {}
+
+/// Missing schema / value blocks and required fields without a value.
+fn structural_errors(doc: &AlnDocument) -> Vec<ShardLoadError> {
+    let mut errors = Vec::new();
+    match &doc.schema {
+        None => errors.push(ShardLoadError::MissingSchema),
+        Some(schema) => {
+            for spec in schema.required_fields() {
+                if doc.value(&spec.name).is_none() {
+                    errors.push(ShardLoadError::MissingRequiredField {
+                        field: spec.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+    if doc.values.is_empty() {
+        errors.push(ShardLoadError::MissingValues);
+    }
+    errors
+}
+
+/// Collects every violation while reading values, instead of stopping at the first.
+struct Hydrator<'a> {
+    doc: &'a AlnDocument,
+    errors: Vec<ShardLoadError>,
+}
+
+impl<'a> Hydrator<'a> {
+    fn value(&mut self, field: &str) -> Option<&'a AlnValue> {
+        let value = self.doc.value(field)?;
+        if let Some(spec) = self.doc.field_spec(field) {
+            if let Err(e) = value.check(spec.datatype) {
+                self.errors.push(ShardLoadError::InvalidValue(e));
+                return None;
+            }
+        }
+        Some(value)
+    }
+
+    fn string(&mut self, field: &str) -> Option<String> {
+        self.value(field).map(|v| v.as_str().to_string())
+    }
+
+    fn bool(&mut self, field: &str) -> Option<bool> {
+        let v = self.value(field)?;
+        self.ok(v.as_bool())
+    }
+
+    fn uint(&mut self, field: &str) -> Option<u64> {
+        let v = self.value(field)?;
+        self.ok(v.as_u64())
+    }
+
+    fn u32(&mut self, field: &str) -> Option<u32> {
+        let raw = self.uint(field)?;
+        match u32::try_from(raw) {
+            Ok(v) => Some(v),
+            Err(_) => {
+                self.errors.push(ShardLoadError::OutOfRange {
+                    field: field.to_string(),
+                    value: raw as f64,
+                    min: 0.0,
+                    max: u32::MAX as f64,
+                });
+                None
+            }
+        }
+    }
+
+    /// Float constrained to the normalized [0, 1] band used by every ALN score.
+    fn unit_float(&mut self, field: &str) -> Option<f32> {
+        let v = self.value(field)?;
+        let raw = self.ok(v.as_f64())?;
+        if !(0.0..=1.0).contains(&raw) {
+            self.errors.push(ShardLoadError::OutOfRange {
+                field: field.to_string(),
+                value: raw,
+                min: 0.0,
+                max: 1.0,
+            });
+            return None;
+        }
+        Some(raw as f32)
+    }
+
+    fn enumerated<T>(
+        &mut self,
+        field: &str,
+        expected: &'static str,
+        parse: fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        let v = self.value(field)?;
+        match parse(v.as_str()) {
+            Some(t) => Some(t),
+            None => {
+                self.errors.push(ShardLoadError::InvalidEnum {
+                    field: field.to_string(),
+                    raw: v.raw.clone(),
+                    expected,
+                });
+                None
+            }
+        }
+    }
+
+    /// `v`, reporting the field as missing if it has no value and the schema
+    /// didn't already demand one.
+    fn require<T>(&mut self, field: &str, v: Option<T>) -> Option<T> {
+        if v.is_none() && self.doc.value(field).is_none() {
+            let missing = ShardLoadError::MissingRequiredField {
+                field: field.to_string(),
+            };
+            if !self.errors.contains(&missing) {
+                self.errors.push(missing);
+            }
+        }
+        v
+    }
+
+    fn ok<T>(&mut self, r: Result<T, AlnValueError>) -> Option<T> {
+        match r {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.errors.push(ShardLoadError::InvalidValue(e));
+                None
+            }
+        }
+    }
+}
+
+impl ControlMode {
+    /// Parse the ALN `controlmode` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "internalbiophysical" => Some(ControlMode::InternalBiophysical),
+            "externalswitch" => Some(ControlMode::ExternalSwitch),
+            "voice" => Some(ControlMode::Voice),
+            "mixed" => Some(ControlMode::Mixed),
+            _ => None,
+        }
+    }
+}
+
+impl AiConsentPolicy {
+    /// Parse the ALN `aiconsentpolicy` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "conservative" => Some(AiConsentPolicy::Conservative),
+            "balanced" => Some(AiConsentPolicy::Balanced),
+            "caregivercoapproval" => Some(AiConsentPolicy::CaregiverCoapproval),
+            _ => None,
+        }
+    }
+}
+
+impl PromptAccounting {
+    /// Parse the ALN `promptaccounting` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "allowonly" => Some(PromptAccounting::AllowOnly),
+            "allowanddefer" => Some(PromptAccounting::AllowAndDefer),
+            "allsurfaced" => Some(PromptAccounting::AllSurfaced),
+            _ => None,
+        }
+    }
+}
+
+impl AuditRedaction {
+    /// Parse the ALN `auditredaction` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "raw" => Some(AuditRedaction::Raw),
+            "bands" => Some(AuditRedaction::Bands),
+            "commitments" => Some(AuditRedaction::Commitments),
+            _ => None,
+        }
+    }
+}
+
+impl AuStatus {
+    /// Parse the ALN `au_status` spelling; the compact form is what older POS
+    /// configs used.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "organically_integrated_augmented_citizen" | "organicallyintegratedaugmentedcitizen" => {
+                Some(AuStatus::OrganicallyIntegratedAugmentedCitizen)
+            }
+            "non_augmented" => Some(AuStatus::NonAugmented),
+            "other" => Some(AuStatus::Other),
+            _ => None,
+        }
+    }
+}
+
+impl PreferredConsentMode {
+    /// Parse the ALN `preferred_consent_mode` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "bcistate" => Some(PreferredConsentMode::BciState),
+            "xr_visual" => Some(PreferredConsentMode::XrVisual),
+            "external_device" => Some(PreferredConsentMode::ExternalDevice),
+            "caregiver_coapproval" => Some(PreferredConsentMode::CaregiverCoapproval),
+            _ => None,
+        }
+    }
+}
+
+impl InterfacePrimary {
+    /// Parse the ALN `interface_primary` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "implanted_nfc" => Some(InterfacePrimary::ImplantedNfc),
+            "xr_companion" => Some(InterfacePrimary::XrCompanion),
+            "phone" => Some(InterfacePrimary::Phone),
+            "caregiver_proxy" => Some(InterfacePrimary::CaregiverProxy),
+            _ => None,
+        }
+    }
+}
+
+impl FeedbackLabel {
+    /// Parse the ALN `feedback_label` spelling.
+    pub fn from_aln(raw: &str) -> Option<Self> {
+        match raw {
+            "ok" => Some(FeedbackLabel::Ok),
+            "borderline" => Some(FeedbackLabel::Borderline),
+            "too_much" => Some(FeedbackLabel::TooMuch),
+            "felt_wrong" => Some(FeedbackLabel::FeltWrong),
+            "rights_violation_suspected" => Some(FeedbackLabel::RightsViolationSuspected),
+            _ => None,
+        }
+    }
+}
+
+impl AugFingerprintShard {
+    /// Hydrate a shard from a parsed `au_augfingerprint_wallet` qpudatashard.
+    ///
+    /// Every `required=true` schema field must carry a value, all values must match
+    /// their declared datatype, normalized scores must lie in [0, 1] and neither the
+    /// S_t corridor nor the latency band may be inverted. All violations are
+    /// returned together.
+    /// Fields that are optional and absent keep the defaults from `new`.
+    pub fn from_aln(doc: &AlnDocument, now: SystemTime) -> Result<Self, Vec<ShardLoadError>> {
+        let mut h = Hydrator {
+            doc,
+            errors: structural_errors(doc),
+        };
+        let wallet_did = h.string("walletdid").unwrap_or_default();
+        let mut shard = AugFingerprintShard::new(wallet_did, now);
+
+        if let Some(v) = h.string("interfacetype") {
+            shard.interface_type = v;
+        }
+        if let Some(v) = h.enumerated(
+            "controlmode",
+            "internalbiophysical externalswitch voice mixed",
+            ControlMode::from_aln,
+        ) {
+            shard.control_mode = v;
+        }
+        if let Some(v) = h.bool("assistmode") {
+            shard.assist_mode = v;
+        }
+        if let Some(v) = h.bool("directusergesture") {
+            shard.direct_user_gesture = v;
+        }
+        if let Some(v) = h.string("consentchannel") {
+            shard.consent_channel = v;
+        }
+        if let Some(v) = h.bool("quantifiedlearning") {
+            shard.quantified_learning = v;
+        }
+
+        if let Some(v) = h.bool("speechreliable") {
+            shard.speech_reliable = v;
+        }
+        if let Some(v) = h.string("mobilityprofile") {
+            shard.mobility_profile = v;
+        }
+        if let Some(v) = h.bool("oculuscortexcalibrated") {
+            shard.oculus_cortex_calibrated = v;
+        }
+        if let Some(v) = h.string("latencyprofile") {
+            shard.latency_profile = v;
+        }
+        if let Some(v) = h.unit_float("maxcognitiveload") {
+            shard.max_cognitive_load = v;
+        }
+        if let Some(v) = h.uint("latencytolerancemsmin") {
+            shard.latency_ms_min = v;
+        }
+        if let Some(v) = h.uint("latencytolerancemsmax") {
+            shard.latency_ms_max = v;
+        }
+        // Checked after defaults apply, so a lone minimum can't overtake the default maximum.
+        if shard.latency_ms_min > shard.latency_ms_max {
+            h.errors.push(ShardLoadError::LatencyBandInverted {
+                min: shard.latency_ms_min,
+                max: shard.latency_ms_max,
+            });
+        }
+        if let Some(v) = h.unit_float("weight_bcistate") {
+            shard.channel_weights.bci_state = v;
+        }
+        if let Some(v) = h.unit_float("weight_externalswitch") {
+            shard.channel_weights.external_switch = v;
+        }
+        if let Some(v) = h.unit_float("weight_voice") {
+            shard.channel_weights.voice = v;
+        }
+        if let Some(v) = h.unit_float("weight_xrvisual") {
+            shard.channel_weights.xr_visual = v;
+        }
+        if let Some(v) = h.unit_float("fusionminconfidence") {
+            shard.fusion_min_confidence = v;
+        }
+
+        if let Some(v) = h.uint("maxautoamountmills") {
+            shard.max_auto_amount_mills = v;
+        }
+        if let Some(v) = h.uint("maxdailyspendmills") {
+            shard.max_daily_spend_mills = v;
+        }
+        if let Some(v) = h.u32("maxpaymentsperhour") {
+            shard.max_payments_per_hour = v;
+        }
+        if let Some(v) = h.u32("maxpromptsperhour") {
+            shard.max_prompts_per_hour = v;
+        }
+        if let Some(v) = h.enumerated(
+            "promptaccounting",
+            "allowonly allowanddefer allsurfaced",
+            PromptAccounting::from_aln,
+        ) {
+            shard.prompt_accounting = v;
+        }
+
+        if let Some(v) = h.string("learningmode") {
+            shard.learning_mode = v;
+        }
+        if let Some(v) = h.enumerated(
+            "aiconsentpolicy",
+            "conservative balanced caregivercoapproval",
+            AiConsentPolicy::from_aln,
+        ) {
+            shard.ai_consent_policy = v;
+            shard.policy_thresholds = v.default_thresholds();
+        }
+        if let Some(v) = h.uint("minstabilitytimems") {
+            shard.policy_thresholds.min_stability_time = Duration::from_millis(v);
+        }
+        if let Some(v) = h.unit_float("rprivacymax") {
+            shard.policy_thresholds.r_privacy_max = v;
+        }
+        if let Some(v) = h.unit_float("rfraudmax") {
+            shard.policy_thresholds.r_fraud_max = v;
+        }
+        if let Some(v) = h.unit_float("rtrackingmax") {
+            shard.policy_thresholds.r_tracking_max = v;
+        }
+        if let Some(v) = h.uint("recoverycooldownms") {
+            shard.recovery_cooldown = Duration::from_millis(v);
+        }
+        if let Some(v) = h.uint("neurostatemaxagems") {
+            shard.neuro_state_max_age = Duration::from_millis(v);
+        }
+
+        if let Some(v) = h.string("caregiverdid") {
+            if !v.is_empty() {
+                shard.appoint_caregiver(v, now);
+            }
+        }
+        if let Some(v) = h.uint("coapprovaltimeoutms") {
+            shard.coapproval_timeout = Duration::from_millis(v);
+        }
+        if let Some(v) = h.uint("coapprovalthresholdmills") {
+            shard.coapproval_threshold_mills = v;
+        }
+
+        if let Some(v) = h.unit_float("rprivacy") {
+            shard.r_privacy = v;
+        }
+        if let Some(v) = h.unit_float("rfraud") {
+            shard.r_fraud = v;
+        }
+        if let Some(v) = h.unit_float("rtracking") {
+            shard.r_tracking = v;
+        }
+        if let Some(v) = h.unit_float("eaccessibility") {
+            shard.e_accessibility = v;
+        }
+
+        let smin = h.unit_float("neuros_smin");
+        let smax = h.unit_float("neuros_smax");
+        if let Some(v) = smin {
+            shard.neuro_state.smin = v;
+        }
+        if let Some(v) = smax {
+            shard.neuro_state.smax = v;
+        }
+        if let (Some(smin), Some(smax)) = (smin, smax) {
+            if smin > smax {
+                h.errors.push(ShardLoadError::CorridorInverted { smin, smax });
+            }
+        }
+        if let Some(v) = h.unit_float("neurol_loadmax") {
+            shard.neuro_state.loadmax = v;
+        }
+        if let Some(v) = h.unit_float("neuros_hysteresis") {
+            shard.corridor_s_hysteresis = v;
+        }
+        if let Some(v) = h.unit_float("neurol_hysteresis") {
+            shard.corridor_load_hysteresis = v;
+        }
+        if let Some(v) = h.uint("corridorexitdebouncems") {
+            shard.stability.exit_debounce = Duration::from_millis(v);
+        }
+
+        if let Some(v) = h.bool("consentauditlogenabled") {
+            shard.consent_audit_log_enabled = v;
+        }
+        if let Some(v) = h.enumerated("auditredaction", "raw bands commitments", AuditRedaction::from_aln) {
+            shard.audit_redaction = v;
+        }
+
+        if h.errors.is_empty() {
+            Ok(shard)
+        } else {
+            Err(h.errors)
+        }
+    }
+}
+
+impl AugFingerprintCorridor {
+    /// Hydrate the POS prompt corridor from a parsed `au_org_integrated_citizen_compat`
+    /// qpudatashard.
+    ///
+    /// Same rules as `AugFingerprintShard::from_aln`: required fields present,
+    /// datatypes and enum spellings valid, reliabilities in [0, 1] and the latency
+    /// band not inverted. All violations are returned together. `episode`-scope
+    /// fields are per-interaction feedback, not corridor settings, and are ignored.
+    /// Absent per-class budgets fall back to `max_prompts_per_hour`, and an absent
+    /// high-stakes threshold to 50.000 USD.
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, Vec<ShardLoadError>> {
+        let mut h = Hydrator {
+            doc,
+            errors: structural_errors(doc),
+        };
+
+        let v = h.enumerated(
+            "au_status",
+            "organically_integrated_augmented_citizen non_augmented other",
+            AuStatus::from_aln,
+        );
+        let austatus = h.require("au_status", v);
+        let v = h.unit_float("cap_input_speech");
+        let cap_input_speech = h.require("cap_input_speech", v);
+        let v = h.unit_float("cap_input_internal_bio");
+        let cap_input_internal_bio = h.require("cap_input_internal_bio", v);
+        let v = h.uint("latency_tolerance_ms_min");
+        let latency_ms_min = h.require("latency_tolerance_ms_min", v);
+        let v = h.uint("latency_tolerance_ms_max");
+        let latency_ms_max = h.require("latency_tolerance_ms_max", v);
+        let v = h.u32("max_decisions_per_hour");
+        let max_decisions_per_hour = h.require("max_decisions_per_hour", v);
+        let v = h.u32("max_prompts_per_hour");
+        let max_prompts_per_hour = h.require("max_prompts_per_hour", v);
+        let max_informational_per_hour = h.u32("max_informational_per_hour");
+        let max_low_value_payments_per_hour = h.u32("max_low_value_payments_per_hour");
+        let high_stakes_threshold_mills = h.uint("high_stakes_threshold_mills");
+        let v = h.enumerated(
+            "preferred_consent_mode",
+            "bcistate xr_visual external_device caregiver_coapproval",
+            PreferredConsentMode::from_aln,
+        );
+        let preferred_consent_mode = h.require("preferred_consent_mode", v);
+        let v = h.enumerated(
+            "interface_primary",
+            "implanted_nfc xr_companion phone caregiver_proxy",
+            InterfacePrimary::from_aln,
+        );
+        let interface_primary = h.require("interface_primary", v);
+        let v = h.bool("no_exclusion_basic_services");
+        let no_exclusion_basic_services = h.require("no_exclusion_basic_services", v);
+
+        if let (Some(min), Some(max)) = (latency_ms_min, latency_ms_max) {
+            if min > max {
+                h.errors.push(ShardLoadError::LatencyBandInverted { min, max });
+            }
+        }
+
+        // Every field is required, so any `None` here has been reported above.
+        match (
+            austatus,
+            cap_input_speech,
+            cap_input_internal_bio,
+            latency_ms_min,
+            latency_ms_max,
+            max_decisions_per_hour,
+            max_prompts_per_hour,
+            preferred_consent_mode,
+            interface_primary,
+            no_exclusion_basic_services,
+        ) {
+            (
+                Some(austatus),
+                Some(cap_input_speech),
+                Some(cap_input_internal_bio),
+                Some(latency_ms_min),
+                Some(latency_ms_max),
+                Some(max_decisions_per_hour),
+                Some(max_prompts_per_hour),
+                Some(preferred_consent_mode),
+                Some(interface_primary),
+                Some(no_exclusion_basic_services),
+            ) if h.errors.is_empty() => Ok(AugFingerprintCorridor {
+                austatus,
+                cap_input_speech,
+                cap_input_internal_bio,
+                latency_ms_min,
+                latency_ms_max,
+                max_decisions_per_hour,
+                max_prompts_per_hour,
+                max_informational_per_hour: max_informational_per_hour
+                    .unwrap_or(max_prompts_per_hour),
+                max_low_value_payments_per_hour: max_low_value_payments_per_hour
+                    .unwrap_or(max_prompts_per_hour),
+                high_stakes_threshold_mills: high_stakes_threshold_mills.unwrap_or(50_000),
+                preferred_consent_mode,
+                interface_primary,
+                no_exclusion_basic_services,
+            }),
+            _ => Err(h.errors),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::at;
+
+    const WALLET: &str = include_str!("../qpudatashards/au_augfingerprint_wallet_2026.aln");
+    const COMPAT: &str = include_str!("../qpudatashards/au_org_integrated_citizen_compat_2026.aln");
+
+    /// The wallet shard with each `(from, to)` value line replaced.
+    fn wallet_with(edits: &[(&str, &str)]) -> AlnDocument {
+        let mut text = WALLET.to_string();
+        for (from, to) in edits {
+            assert!(text.contains(from), "{}", from);
+            text = text.replacen(from, to, 1);
+        }
+        AlnDocument::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn hydrates_the_shipped_wallet_shard() {
+        let shard = AugFingerprintShard::from_aln(&wallet_with(&[]), at(0)).unwrap();
+        assert_eq!(shard.wallet_did, "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7");
+        assert_eq!(shard.ai_consent_policy, AiConsentPolicy::Conservative);
+        assert_eq!(shard.policy_thresholds.min_stability_time, Duration::from_secs(5));
+        assert_eq!((shard.latency_ms_min, shard.latency_ms_max), (500, 3_000));
+        assert_eq!(shard.stability.exit_debounce, Duration::from_secs(2));
+        assert_eq!(shard.audit_redaction, AuditRedaction::Bands);
+    }
+
+    #[test]
+    fn reports_every_violation_together() {
+        let doc = wallet_with(&[
+            ("walletdid,\"bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7\"\n", ""),
+            ("rprivacy,0.2\n", "rprivacy,1.5\n"),
+            ("neuros_smin,0.4\n", "neuros_smin,0.7\n"),
+        ]);
+        let errors = AugFingerprintShard::from_aln(&doc, at(0)).unwrap_err();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors.contains(&ShardLoadError::MissingRequiredField {
+            field: "walletdid".into()
+        }));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ShardLoadError::OutOfRange { field, .. } if field == "rprivacy"
+        )));
+        assert!(errors.contains(&ShardLoadError::CorridorInverted { smin: 0.7, smax: 0.6 }));
+    }
+
+    #[test]
+    fn rejects_an_inverted_latency_band() {
+        let doc = wallet_with(&[("latencytolerancemsmin,500\n", "latencytolerancemsmin,4000\n")]);
+        let errors = AugFingerprintShard::from_aln(&doc, at(0)).unwrap_err();
+        assert_eq!(errors, vec![ShardLoadError::LatencyBandInverted { min: 4_000, max: 3_000 }]);
+    }
+
+    #[test]
+    fn bad_enum_spelling_is_reported_with_the_accepted_ones() {
+        let doc = wallet_with(&[("aiconsentpolicy,\"conservative\"", "aiconsentpolicy,\"lenient\"")]);
+        let errors = AugFingerprintShard::from_aln(&doc, at(0)).unwrap_err();
+        assert!(matches!(
+            &errors[..],
+            [ShardLoadError::InvalidEnum { field, raw, .. }] if field == "aiconsentpolicy" && raw == "lenient"
+        ));
+    }
+
+    #[test]
+    fn hydrates_the_shipped_compat_corridor() {
+        let corridor = AugFingerprintCorridor::from_aln(&AlnDocument::parse(COMPAT).unwrap()).unwrap();
+        assert!(corridor.applies());
+        assert_eq!(corridor.max_decisions_per_hour, 3);
+        assert_eq!(corridor.max_informational_per_hour, 6);
+        assert_eq!(corridor.interface_primary, InterfacePrimary::ImplantedNfc);
+    }
+}
@@ -0,0 +1,125 @@
+/// Augmentation status from the org-integrated citizen compat shard (`au_status`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuStatus {
+    OrganicallyIntegratedAugmentedCitizen,
+    NonAugmented,
+    Other,
+}
+
+/// Consent channel the host prefers to be asked on (`preferred_consent_mode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredConsentMode {
+    BciState,
+    XrVisual,
+    ExternalDevice,
+    CaregiverCoapproval,
+}
+
+/// Primary payment interface (`interface_primary`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfacePrimary {
+    ImplantedNfc,
+    XrCompanion,
+    Phone,
+    CaregiverProxy,
+}
+
+/// Stakes class of a prompt; each class has its own hourly budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptStakes {
+    /// Receipts, balance or status notices; nothing is decided.
+    Informational,
+    /// Reversible payment at or below `high_stakes_threshold_mills`.
+    LowValuePayment,
+    /// Payment above the threshold, or anything irreversible.
+    HighStakes,
+}
+
+/// POS-side prompt corridor for one host, hydrated from
+/// `au_org_integrated_citizen_compat_2026.aln` (see `AugFingerprintCorridor::from_aln`).
+///
+/// `AugCitizenPosGuard` uses it to decide whether, and how often, a payment
+/// prompt may be presented at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AugFingerprintCorridor {
+    pub austatus: AuStatus,
+    /// 0.0–1.0 reliability of the speech channel.
+    pub cap_input_speech: f32,
+    /// 0.0–1.0 reliability of internal biophysical control.
+    pub cap_input_internal_bio: f32,
+    /// Comfortable round-trip latency band.
+    pub latency_ms_min: u64,
+    pub latency_ms_max: u64,
+    /// Safe bound on high-stakes decisions per hour.
+    pub max_decisions_per_hour: u32,
+    /// Safe bound on any prompts per hour (merchants may only tighten it).
+    pub max_prompts_per_hour: u32,
+    pub max_informational_per_hour: u32,
+    pub max_low_value_payments_per_hour: u32,
+    /// Amount above which a payment prompt is `PromptStakes::HighStakes`.
+    pub high_stakes_threshold_mills: u64,
+    pub preferred_consent_mode: PreferredConsentMode,
+    pub interface_primary: InterfacePrimary,
+    pub no_exclusion_basic_services: bool,
+}
+
+impl AugFingerprintCorridor {
+    /// Whether the augmented-citizen prompt corridor applies to this host at all.
+    pub fn applies(&self) -> bool {
+        self.austatus == AuStatus::OrganicallyIntegratedAugmentedCitizen
+    }
+
+    /// Hourly budget of one stakes class; every class also counts against
+    /// `max_prompts_per_hour`.
+    pub fn stakes_budget(&self, stakes: PromptStakes) -> u32 {
+        match stakes {
+            PromptStakes::Informational => self.max_informational_per_hour,
+            PromptStakes::LowValuePayment => self.max_low_value_payments_per_hour,
+            PromptStakes::HighStakes => self.max_decisions_per_hour,
+        }
+    }
+
+    /// Stakes class of a payment prompt.
+    pub fn classify_payment(&self, amount_mills: u64, irreversible: bool) -> PromptStakes {
+        if irreversible || amount_mills > self.high_stakes_threshold_mills {
+            PromptStakes::HighStakes
+        } else {
+            PromptStakes::LowValuePayment
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::pos_corridor as corridor;
+
+    #[test]
+    fn applies_only_to_integrated_citizens() {
+        let mut c = corridor();
+        assert!(c.applies());
+        for status in [AuStatus::NonAugmented, AuStatus::Other] {
+            c.austatus = status;
+            assert!(!c.applies(), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn each_stakes_class_has_its_own_budget() {
+        let c = AugFingerprintCorridor {
+            max_low_value_payments_per_hour: 5,
+            ..corridor()
+        };
+        assert_eq!(c.stakes_budget(PromptStakes::Informational), 6);
+        assert_eq!(c.stakes_budget(PromptStakes::LowValuePayment), 5);
+        assert_eq!(c.stakes_budget(PromptStakes::HighStakes), 3);
+    }
+
+    #[test]
+    fn payments_above_the_threshold_or_irreversible_are_high_stakes() {
+        let c = corridor();
+        assert_eq!(c.classify_payment(50_000, false), PromptStakes::LowValuePayment);
+        assert_eq!(c.classify_payment(50_001, false), PromptStakes::HighStakes);
+        assert_eq!(c.classify_payment(1, true), PromptStakes::HighStakes);
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use crate::consent_policy::Conservative;
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentState, AugFingerprintGuard, AugFingerprintShard, ConsentDecision, ConsentOutcome,
+    ConsentReason, PaymentRequest,
+};
+
+/// A caregiver appointed by the host as second approver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaregiverDelegation {
+    pub caregiver_did: String,
+    pub granted_at: SystemTime,
+    /// Set when the host revokes the caregiver; a revoked delegation never signs again.
+    pub revoked_at: Option<SystemTime>,
+}
+
+impl CaregiverDelegation {
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+/// Payment waiting for the caregiver's co-signature.
+#[derive(Debug, Clone)]
+pub struct PendingApproval {
+    pub approval_id: u64,
+    pub caregiver_did: String,
+    pub request: PaymentRequest,
+    pub ai_consent_state: Option<AiConsentState>,
+    pub requested_at: SystemTime,
+    pub expires_at: SystemTime,
+}
+
+/// Why a co-signature was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoapprovalError {
+    /// No caregiver is appointed, or the host revoked them.
+    NoActiveCaregiver,
+    /// The signer is not the appointed caregiver.
+    WrongCaregiver { signer_did: String },
+    /// No pending approval with this id (never opened, already settled or revoked).
+    UnknownApproval { approval_id: u64 },
+    /// The caregiver answered after `expires_at`; the approval has been dropped.
+    Expired { approval_id: u64 },
+}
+
+impl fmt::Display for CoapprovalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoapprovalError::NoActiveCaregiver => write!(f, "no active caregiver"),
+            CoapprovalError::WrongCaregiver { signer_did } => {
+                write!(f, "`{}` is not the appointed caregiver", signer_did)
+            }
+            CoapprovalError::UnknownApproval { approval_id } => {
+                write!(f, "no pending approval {}", approval_id)
+            }
+            CoapprovalError::Expired { approval_id } => {
+                write!(f, "approval {} expired before it was co-signed", approval_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoapprovalError {}
+
+/// Default time a caregiver has to co-sign a pending approval.
+pub const DEFAULT_COAPPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+impl AugFingerprintShard {
+    /// The appointed caregiver, unless revoked.
+    pub fn active_caregiver(&self) -> Option<&CaregiverDelegation> {
+        self.caregiver.as_ref().filter(|c| c.is_active())
+    }
+
+    /// Appoint `caregiver_did` as co-approver, replacing any previous caregiver.
+    /// Pending approvals addressed to a previous caregiver are dropped.
+    pub fn appoint_caregiver(&mut self, caregiver_did: String, now: SystemTime) {
+        self.pending_approvals.retain(|p| p.caregiver_did == caregiver_did);
+        self.caregiver = Some(CaregiverDelegation {
+            caregiver_did,
+            granted_at: now,
+            revoked_at: None,
+        });
+    }
+
+    /// Host revocation; always honoured. Returns the approvals that were cancelled.
+    pub fn revoke_caregiver(&mut self, now: SystemTime) -> Vec<PendingApproval> {
+        if let Some(c) = self.caregiver.as_mut() {
+            c.revoked_at.get_or_insert(now);
+        }
+        std::mem::take(&mut self.pending_approvals)
+    }
+
+    /// Drop approvals whose timeout has passed and return them.
+    pub fn expire_coapprovals(&mut self, now: SystemTime) -> Vec<PendingApproval> {
+        let (expired, live) = std::mem::take(&mut self.pending_approvals)
+            .into_iter()
+            .partition(|p| now > p.expires_at);
+        self.pending_approvals = live;
+        expired
+    }
+
+    /// Open a pending approval for the active caregiver; `None` if there is none.
+    pub(crate) fn open_coapproval(
+        &mut self,
+        request: &PaymentRequest,
+        ai_consent_state: Option<AiConsentState>,
+    ) -> Option<u64> {
+        let caregiver_did = self.active_caregiver()?.caregiver_did.clone();
+        self.expire_coapprovals(request.now);
+        let approval_id = self.next_approval_id;
+        self.next_approval_id = self.next_approval_id.wrapping_add(1);
+        self.pending_approvals.push(PendingApproval {
+            approval_id,
+            caregiver_did,
+            request: request.clone(),
+            ai_consent_state,
+            requested_at: request.now,
+            expires_at: request.now + self.coapproval_timeout,
+        });
+        Some(approval_id)
+    }
+
+    fn take_coapproval(
+        &mut self,
+        approval_id: u64,
+        signer_did: &str,
+        now: SystemTime,
+    ) -> Result<PendingApproval, CoapprovalError> {
+        let caregiver = self
+            .active_caregiver()
+            .ok_or(CoapprovalError::NoActiveCaregiver)?;
+        if caregiver.caregiver_did != signer_did {
+            return Err(CoapprovalError::WrongCaregiver {
+                signer_did: signer_did.to_string(),
+            });
+        }
+        let idx = self
+            .pending_approvals
+            .iter()
+            .position(|p| p.approval_id == approval_id)
+            .ok_or(CoapprovalError::UnknownApproval { approval_id })?;
+        let pending = self.pending_approvals.remove(idx);
+        if now > pending.expires_at {
+            return Err(CoapprovalError::Expired { approval_id });
+        }
+        Ok(pending)
+    }
+}
+
+impl AugFingerprintGuard {
+    /// Caregiver co-signs a pending approval at `now`.
+    ///
+    /// The co-signature only replaces the second-approver requirement: the request
+    /// is re-checked at `now` against the rights-level checks and the Conservative
+    /// risk ceilings, so a host that left the corridor meanwhile is still refused.
+    pub fn cosign(
+        shard: &mut AugFingerprintShard,
+        approval_id: u64,
+        caregiver_did: &str,
+        now: SystemTime,
+    ) -> Result<ConsentOutcome, CoapprovalError> {
+        let pending = shard.take_coapproval(approval_id, caregiver_did, now)?;
+        let mut request = pending.request;
+        request.now = now;
+        let policy = Conservative {
+            thresholds: shard.policy_thresholds,
+        };
+        let (decision, reason) = Self::decide(shard, &request, pending.ai_consent_state, &policy);
+        Ok(Self::settle(
+            shard,
+            &request,
+            pending.ai_consent_state,
+            (decision, reason),
+            policy.thresholds.min_stability_time,
+            Some(approval_id),
+            Some(pending.caregiver_did),
+        ))
+    }
+
+    /// Caregiver declines a pending approval at `now`; the payment is denied.
+    pub fn decline(
+        shard: &mut AugFingerprintShard,
+        approval_id: u64,
+        caregiver_did: &str,
+        now: SystemTime,
+    ) -> Result<ConsentOutcome, CoapprovalError> {
+        let pending = shard.take_coapproval(approval_id, caregiver_did, now)?;
+        let mut request = pending.request;
+        request.now = now;
+        let min_stability_time = shard.policy_thresholds.min_stability_time;
+        Ok(Self::settle(
+            shard,
+            &request,
+            pending.ai_consent_state,
+            (ConsentDecision::Deny, ConsentReason::CaregiverDeclined),
+            min_stability_time,
+            Some(approval_id),
+            Some(pending.caregiver_did),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::AiConsentPolicy;
+    use crate::test_support::{at, pay, sample, shard};
+
+    const CAREGIVER: &str = "did:aln:caregiver";
+
+    /// Steady host under the co-approval policy with one approval opened at t=20.
+    fn pending() -> (AugFingerprintShard, u64) {
+        let mut shard = shard();
+        shard.ai_consent_policy = AiConsentPolicy::CaregiverCoapproval;
+        shard.appoint_caregiver(CAREGIVER.into(), at(0));
+        shard.update_neuro_state(sample(0.5, 0));
+        shard.update_neuro_state(sample(0.5, 20));
+        let request = pay(1_000, false, 20);
+        let outcome = AugFingerprintGuard::evaluate_outcome(&mut shard, &request, None);
+        assert_eq!(outcome.reason, ConsentReason::CaregiverApprovalRequired);
+        let approval_id = outcome.audit.unwrap().approval_id.unwrap();
+        (shard, approval_id)
+    }
+
+    #[test]
+    fn cosigned_payment_is_allowed_and_audited_with_the_caregiver() {
+        let (mut shard, id) = pending();
+        shard.update_neuro_state(sample(0.5, 40));
+        let outcome = AugFingerprintGuard::cosign(&mut shard, id, CAREGIVER, at(40)).unwrap();
+        assert_eq!((outcome.decision, outcome.reason), (ConsentDecision::Allow, ConsentReason::Ok));
+        let audit = outcome.audit.unwrap();
+        assert_eq!(audit.approval_id, Some(id));
+        assert_eq!(audit.co_approver_did.as_deref(), Some(CAREGIVER));
+        assert!(shard.pending_approvals.is_empty());
+        assert_eq!(shard.payments_last_hour(at(40)), 1);
+    }
+
+    #[test]
+    fn cosign_rechecks_the_host_state() {
+        let (mut shard, id) = pending();
+        shard.update_neuro_state(sample(0.9, 30));
+        shard.update_neuro_state(sample(0.9, 40));
+        let outcome = AugFingerprintGuard::cosign(&mut shard, id, CAREGIVER, at(40)).unwrap();
+        assert_eq!(outcome.decision, ConsentDecision::Deny);
+        assert_eq!(outcome.reason, ConsentReason::StateOutsideCorridor);
+    }
+
+    #[test]
+    fn only_the_appointed_caregiver_can_answer_in_time() {
+        let (mut shard, id) = pending();
+        assert_eq!(
+            AugFingerprintGuard::cosign(&mut shard, id, "did:aln:other", at(30)).unwrap_err(),
+            CoapprovalError::WrongCaregiver {
+                signer_did: "did:aln:other".into()
+            }
+        );
+        let late = at(20) + DEFAULT_COAPPROVAL_TIMEOUT + Duration::from_secs(1);
+        assert_eq!(
+            AugFingerprintGuard::cosign(&mut shard, id, CAREGIVER, late).unwrap_err(),
+            CoapprovalError::Expired { approval_id: id }
+        );
+        assert_eq!(
+            AugFingerprintGuard::cosign(&mut shard, id, CAREGIVER, late).unwrap_err(),
+            CoapprovalError::UnknownApproval { approval_id: id }
+        );
+    }
+
+    #[test]
+    fn decline_denies_the_payment() {
+        let (mut shard, id) = pending();
+        let outcome = AugFingerprintGuard::decline(&mut shard, id, CAREGIVER, at(30)).unwrap();
+        assert_eq!(
+            (outcome.decision, outcome.reason),
+            (ConsentDecision::Deny, ConsentReason::CaregiverDeclined)
+        );
+        assert_eq!(shard.payments_last_hour(at(30)), 0);
+    }
+
+    #[test]
+    fn revocation_cancels_pending_approvals_and_stops_routing() {
+        let (mut shard, id) = pending();
+        let cancelled = shard.revoke_caregiver(at(30));
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(cancelled[0].approval_id, id);
+        assert_eq!(
+            AugFingerprintGuard::cosign(&mut shard, id, CAREGIVER, at(30)).unwrap_err(),
+            CoapprovalError::NoActiveCaregiver
+        );
+        let request = pay(1_000, false, 30);
+        let (_, reason, _) = AugFingerprintGuard::evaluate(&mut shard, &request, None);
+        assert_eq!(reason, ConsentReason::CaregiverUnavailable);
+    }
+}
@@ -0,0 +1,73 @@
+//! Re-export of `guard_clock`, the `Clock` shared with the lifeforce guard.
+
+pub use guard_clock::{Clock, FixedClock, SimulatedClock, SystemClock};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::augfingerprint_corridor::{AugFingerprintCorridor, PromptStakes};
+    use crate::cybernano_payment_guard::{
+        BloodTokenLedger, CyberNanoPaymentGuard, LifeforceBand, LifeforceEnvelope, NanoDebitClass,
+        NanoDebitRequest,
+    };
+    use crate::paycomp_au_guard::{AugCitizenPosGuard, PromptDecision, PromptState};
+    use crate::paycomp_augfingerprint_guard::AugFingerprintGuard;
+    use crate::test_support::{at, pay, sample, shard};
+    use aln_shard::AlnDocument;
+    use std::time::Duration;
+
+    const COMPAT: &str =
+        include_str!("../qpudatashards/au_org_integrated_citizen_compat_2026.aln");
+
+    #[test]
+    fn wallet_guard_evaluates_at_the_clock_time() {
+        let mut shard = shard();
+        for t in [100, 110, 120] {
+            shard.update_neuro_state(sample(0.5, t));
+        }
+        let request = pay(1_000, false, 0);
+        let clock = FixedClock(at(120));
+        let outcome = AugFingerprintGuard::evaluate_with_clock(&mut shard, &request, None, &clock);
+        assert_eq!(outcome.audit.unwrap().timestamp, at(120));
+    }
+
+    #[test]
+    fn pos_guard_reads_its_clock() {
+        let corridor =
+            AugFingerprintCorridor::from_aln(&AlnDocument::parse(COMPAT).unwrap()).unwrap();
+        let clock = SimulatedClock::new(at(0));
+        let mut guard = AugCitizenPosGuard::new(&corridor, PromptState::new()).with_clock(&clock);
+        for _ in 0..corridor.max_informational_per_hour {
+            let now = guard.now();
+            guard.record_prompt(PromptStakes::Informational, now);
+        }
+        let allowed = |guard: &AugCitizenPosGuard| {
+            let decision = guard.should_prompt_now(PromptStakes::Informational, 1_000);
+            matches!(decision, PromptDecision::Allow { .. })
+        };
+        assert!(!allowed(&guard));
+        clock.advance(Duration::from_secs(3_601));
+        assert!(allowed(&guard));
+    }
+
+    #[test]
+    fn nano_debit_is_stamped_by_the_clock() {
+        let lifeforce = LifeforceEnvelope {
+            lifeforce_level: 0.8,
+            lifeforce_floor: 0.3,
+            lifeforce_curve_margin: 0.05,
+            lifeforce_band: LifeforceBand::Stable,
+        };
+        let ledger = BloodTokenLedger { microusd3_units: 2_500 };
+        let req = NanoDebitRequest {
+            class: NanoDebitClass::MerchantPayment,
+            amount_microusd3: 1_000,
+            estimated_lifeforce_delta: 0.05,
+            local_only: true,
+        };
+        let decision =
+            CyberNanoPaymentGuard::eval_with_clock(&lifeforce, &ledger, &req, &FixedClock(at(7)));
+        assert!(!decision.approved);
+        assert_eq!(decision.decided_at, at(7));
+    }
+}
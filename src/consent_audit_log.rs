@@ -0,0 +1,573 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::consent_recovery::SuspensionAuditRecord;
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentState, AugFingerprintGuard, AugFingerprintShard, ConsentAuditRecord, ConsentOutcome,
+    PaymentRequest,
+};
+
+/// One entry in the consent audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEvent {
+    Consent(ConsentAuditRecord),
+    Suspension(SuspensionAuditRecord),
+}
+
+/// Destination for audit events; implementations must never drop or reorder them.
+pub trait AuditSink {
+    fn append(&mut self, event: &AuditEvent) -> Result<(), AuditLogError>;
+}
+
+/// In-memory sink, for hosts that forward events elsewhere themselves.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAuditSink {
+    pub events: Vec<AuditEvent>,
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn append(&mut self, event: &AuditEvent) -> Result<(), AuditLogError> {
+        self.events.push(event.clone());
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum AuditLogError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The log on disk doesn't match its head file; appending would fork the chain.
+    Chain(ChainViolation),
+}
+
+impl fmt::Display for AuditLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditLogError::Io(e) => write!(f, "audit log i/o: {}", e),
+            AuditLogError::Json(e) => write!(f, "audit log encoding: {}", e),
+            AuditLogError::Chain(v) => write!(f, "audit chain broken: {:?}", v),
+        }
+    }
+}
+
+impl std::error::Error for AuditLogError {}
+
+impl From<io::Error> for AuditLogError {
+    fn from(e: io::Error) -> Self {
+        AuditLogError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AuditLogError {
+    fn from(e: serde_json::Error) -> Self {
+        AuditLogError::Json(e)
+    }
+}
+
+impl From<ChainViolation> for AuditLogError {
+    fn from(v: ChainViolation) -> Self {
+        AuditLogError::Chain(v)
+    }
+}
+
+/// `prev_hash` of the very first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// The hashed part of a log line.
+#[derive(Serialize)]
+struct ChainBody<'a> {
+    seq: u64,
+    prev_hash: &'a str,
+    event: &'a serde_json::Value,
+}
+
+/// One JSONL line: `hash = sha256(json(seq, prev_hash, event))`.
+#[derive(Serialize, Deserialize)]
+struct ChainLine {
+    seq: u64,
+    prev_hash: String,
+    event: serde_json::Value,
+    hash: String,
+}
+
+/// Last entry written, mirrored in `<base>.head` so a cut tail is detectable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+    /// Number of entries written so far; the next entry gets this `seq`.
+    pub next_seq: u64,
+    pub last_hash: String,
+    pub segment: u32,
+}
+
+fn chain_hash(seq: u64, prev_hash: &str, event: &serde_json::Value) -> Result<String, AuditLogError> {
+    let body = serde_json::to_vec(&ChainBody {
+        seq,
+        prev_hash,
+        event,
+    })?;
+    Ok(sha256_hex(&body))
+}
+
+/// Lower-case hex SHA-256 of `bytes`.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn segment_path(dir: &Path, base: &str, segment: u32) -> PathBuf {
+    dir.join(format!("{}.{:06}.jsonl", base, segment))
+}
+
+fn head_path(dir: &Path, base: &str) -> PathBuf {
+    dir.join(format!("{}.head", base))
+}
+
+fn read_head(dir: &Path, base: &str) -> Result<Option<ChainHead>, AuditLogError> {
+    match fs::read_to_string(head_path(dir, base)) {
+        Ok(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Check `head` against the entries on disk from its segment onwards.
+///
+/// A crash between an entry's `sync_data` and the head update leaves entries the
+/// head doesn't know about; they are adopted when they extend the chain. A torn
+/// line, a fork or a log shorter than the head is an error, so an entry's `seq` is
+/// never written twice.
+fn reconcile_head(dir: &Path, base: &str, mut head: ChainHead) -> Result<ChainHead, AuditLogError> {
+    let claimed_next_seq = head.next_seq;
+    let mut found_next_seq = 0u64;
+    let mut segment = head.segment;
+    loop {
+        let file = match File::open(segment_path(dir, base, segment)) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        };
+        for (idx, line) in BufReader::new(file).lines().enumerate() {
+            let line_no = idx + 1;
+            let entry: ChainLine = serde_json::from_str(&line?)
+                .map_err(|_| ChainViolation::Malformed { segment, line: line_no })?;
+            found_next_seq = found_next_seq.max(entry.seq + 1);
+            if entry.seq + 1 == head.next_seq {
+                if entry.hash != head.last_hash {
+                    return Err(ChainViolation::HeadMismatch.into());
+                }
+            } else if entry.seq >= head.next_seq {
+                if entry.seq != head.next_seq {
+                    return Err(ChainViolation::SeqGap {
+                        segment,
+                        line: line_no,
+                        expected: head.next_seq,
+                        found: entry.seq,
+                    }
+                    .into());
+                }
+                if entry.prev_hash != head.last_hash {
+                    return Err(ChainViolation::BrokenLink { segment, line: line_no }.into());
+                }
+                if chain_hash(entry.seq, &entry.prev_hash, &entry.event)? != entry.hash {
+                    return Err(ChainViolation::HashMismatch { segment, line: line_no }.into());
+                }
+                head.next_seq += 1;
+                head.last_hash = entry.hash;
+                head.segment = segment;
+            }
+        }
+        segment += 1;
+    }
+    if claimed_next_seq > found_next_seq {
+        return Err(ChainViolation::Truncated {
+            expected_next_seq: claimed_next_seq,
+            found_next_seq,
+        }
+        .into());
+    }
+    Ok(head)
+}
+
+/// File-backed, append-only, hash-chained JSONL audit log.
+///
+/// Entries go to `<dir>/<base>.<segment>.jsonl`; a new segment is started once the
+/// current one reaches `max_segment_bytes`. The chain runs across segments, and
+/// `<dir>/<base>.head` holds the last `seq` / hash so `verify_audit_log` can tell a
+/// truncated tail from a clean end. Files are only ever opened for append.
+pub struct FileAuditSink {
+    dir: PathBuf,
+    base: String,
+    max_segment_bytes: u64,
+    head: ChainHead,
+    file: File,
+    segment_bytes: u64,
+}
+
+impl FileAuditSink {
+    /// Open (or create) the log and resume the chain from its head file, after
+    /// checking the head against the last entries on disk (see `reconcile_head`).
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        base: impl Into<String>,
+        max_segment_bytes: u64,
+    ) -> Result<Self, AuditLogError> {
+        let dir = dir.into();
+        let base = base.into();
+        fs::create_dir_all(&dir)?;
+        let stored = read_head(&dir, &base)?;
+        let head = reconcile_head(
+            &dir,
+            &base,
+            stored.clone().unwrap_or(ChainHead {
+                next_seq: 0,
+                last_hash: GENESIS_HASH.to_string(),
+                segment: 0,
+            }),
+        )?;
+        let path = segment_path(&dir, &base, head.segment);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let segment_bytes = file.metadata()?.len();
+        let sink = Self {
+            dir,
+            base,
+            max_segment_bytes,
+            head,
+            file,
+            segment_bytes,
+        };
+        if stored.as_ref() != Some(&sink.head) && sink.head.next_seq > 0 {
+            sink.write_head()?;
+        }
+        Ok(sink)
+    }
+
+    pub fn head(&self) -> &ChainHead {
+        &self.head
+    }
+
+    fn rotate(&mut self) -> Result<(), AuditLogError> {
+        self.file.sync_all()?;
+        self.head.segment += 1;
+        let path = segment_path(&self.dir, &self.base, self.head.segment);
+        self.file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.segment_bytes = 0;
+        Ok(())
+    }
+
+    fn write_head(&self) -> Result<(), AuditLogError> {
+        let path = head_path(&self.dir, &self.base);
+        let tmp = path.with_extension("head.tmp");
+        fs::write(&tmp, serde_json::to_vec(&self.head)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn append(&mut self, event: &AuditEvent) -> Result<(), AuditLogError> {
+        if self.segment_bytes > 0 && self.segment_bytes >= self.max_segment_bytes {
+            self.rotate()?;
+        }
+        let value = serde_json::to_value(event)?;
+        let seq = self.head.next_seq;
+        let hash = chain_hash(seq, &self.head.last_hash, &value)?;
+        let line = ChainLine {
+            seq,
+            prev_hash: self.head.last_hash.clone(),
+            event: value,
+            hash: hash.clone(),
+        };
+        let mut bytes = serde_json::to_vec(&line)?;
+        bytes.push(b'\n');
+        self.file.write_all(&bytes)?;
+        self.file.sync_data()?;
+        self.segment_bytes += bytes.len() as u64;
+        self.head.next_seq = seq + 1;
+        self.head.last_hash = hash;
+        self.write_head()
+    }
+}
+
+/// Where and how the chain is broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainViolation {
+    /// A segment before the head segment is missing.
+    MissingSegment { segment: u32 },
+    /// Line is not a valid chain entry (including a torn last write).
+    Malformed { segment: u32, line: usize },
+    /// `seq` does not follow the previous entry.
+    SeqGap { segment: u32, line: usize, expected: u64, found: u64 },
+    /// `prev_hash` does not match the previous entry's hash.
+    BrokenLink { segment: u32, line: usize },
+    /// Stored hash does not match the entry's content.
+    HashMismatch { segment: u32, line: usize },
+    /// The log ends before the entry recorded in the head file.
+    Truncated { expected_next_seq: u64, found_next_seq: u64 },
+    /// Last entry's hash differs from the head file.
+    HeadMismatch,
+    /// There are entries but no head file.
+    MissingHead,
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Io(io::Error),
+    Violation(ChainViolation),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Io(e) => write!(f, "audit log i/o: {}", e),
+            VerifyError::Violation(v) => write!(f, "audit chain broken: {:?}", v),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Walk every segment of `<dir>/<base>` and check seq continuity, hash links,
+/// entry hashes and the head file. Returns the verified head.
+pub fn verify_audit_log(dir: &Path, base: &str) -> Result<ChainHead, VerifyError> {
+    let violation = |v| Err(VerifyError::Violation(v));
+    let head = match read_head(dir, base) {
+        Ok(h) => h,
+        Err(AuditLogError::Io(e)) => return Err(VerifyError::Io(e)),
+        Err(AuditLogError::Json(_)) => return violation(ChainViolation::HeadMismatch),
+        Err(AuditLogError::Chain(v)) => return violation(v),
+    };
+    let last_segment = head.as_ref().map_or(0, |h| h.segment);
+
+    let mut next_seq = 0u64;
+    let mut last_hash = GENESIS_HASH.to_string();
+    for segment in 0..=last_segment {
+        let file = match File::open(segment_path(dir, base, segment)) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                if head.is_none() {
+                    break;
+                }
+                return violation(ChainViolation::MissingSegment { segment });
+            }
+            Err(e) => return Err(VerifyError::Io(e)),
+        };
+        for (idx, line) in BufReader::new(file).lines().enumerate() {
+            let line_no = idx + 1;
+            let line = line.map_err(VerifyError::Io)?;
+            let entry: ChainLine = match serde_json::from_str(&line) {
+                Ok(e) => e,
+                Err(_) => return violation(ChainViolation::Malformed { segment, line: line_no }),
+            };
+            if entry.seq != next_seq {
+                return violation(ChainViolation::SeqGap {
+                    segment,
+                    line: line_no,
+                    expected: next_seq,
+                    found: entry.seq,
+                });
+            }
+            if entry.prev_hash != last_hash {
+                return violation(ChainViolation::BrokenLink { segment, line: line_no });
+            }
+            match chain_hash(entry.seq, &entry.prev_hash, &entry.event) {
+                Ok(h) if h == entry.hash => {}
+                _ => return violation(ChainViolation::HashMismatch { segment, line: line_no }),
+            }
+            next_seq += 1;
+            last_hash = entry.hash;
+        }
+    }
+
+    let found = ChainHead {
+        next_seq,
+        last_hash,
+        segment: last_segment,
+    };
+    match head {
+        None if next_seq == 0 => Ok(found),
+        None => violation(ChainViolation::MissingHead),
+        Some(h) if h.next_seq > next_seq => violation(ChainViolation::Truncated {
+            expected_next_seq: h.next_seq,
+            found_next_seq: next_seq,
+        }),
+        Some(h) if h != found => violation(ChainViolation::HeadMismatch),
+        Some(h) => Ok(h),
+    }
+}
+
+impl AugFingerprintGuard {
+    /// `evaluate`, then append any pending suspension transitions and the consent
+    /// record to `sink`.
+    ///
+    /// If the audit write fails the decision is reported as the error instead:
+    /// an outcome that can't be reconstructed later must not be acted on.
+    /// Suspension records leave the shard only once written, so the next call
+    /// retries them. The evaluation itself is not rolled back: an `Allow` has
+    /// already been booked in the payment and spend windows when the error is
+    /// returned, which errs toward refusing later payments, never toward more.
+    pub fn evaluate_logged(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        sink: &mut dyn AuditSink,
+    ) -> Result<ConsentOutcome, AuditLogError> {
+        let outcome = Self::evaluate_outcome(shard, request, ai_state);
+        while let Some(record) = shard.suspension_log.first() {
+            sink.append(&AuditEvent::Suspension(record.clone()))?;
+            shard.suspension_log.remove(0);
+        }
+        if let Some(record) = &outcome.audit {
+            sink.append(&AuditEvent::Consent(record.clone()))?;
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consent_recovery::{SuspensionState, SuspensionTransition};
+    use crate::paycomp_augfingerprint_guard::ConsentReason;
+    use crate::test_support::{at, pay, sample, shard, HOST_DID};
+
+    /// Fresh scratch directory under the system temp dir.
+    fn scratch(name: &str) -> PathBuf {
+        let name = format!("paycomp-audit-{}-{}", std::process::id(), name);
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn event(secs: u64) -> AuditEvent {
+        AuditEvent::Suspension(SuspensionAuditRecord {
+            wallet_did: HOST_DID.into(),
+            transition: SuspensionTransition::Suspended,
+            from: SuspensionState::Active,
+            to: SuspensionState::Suspended { since: at(secs) },
+            timestamp: at(secs),
+        })
+    }
+
+    fn append_all(sink: &mut FileAuditSink, range: std::ops::Range<u64>) {
+        for i in range {
+            sink.append(&event(i)).unwrap();
+        }
+    }
+
+    #[test]
+    fn chain_runs_across_segments_and_reopens_where_it_stopped() {
+        let dir = scratch("rotate");
+        let mut sink = FileAuditSink::open(&dir, "consent", 400).unwrap();
+        append_all(&mut sink, 0..4);
+        assert!(sink.head().segment > 0);
+        drop(sink);
+
+        let mut sink = FileAuditSink::open(&dir, "consent", 400).unwrap();
+        assert_eq!(sink.head().next_seq, 4);
+        append_all(&mut sink, 4..6);
+        let head = verify_audit_log(&dir, "consent").unwrap();
+        assert_eq!(head.next_seq, 6);
+        assert_eq!(&head, sink.head());
+    }
+
+    #[test]
+    fn open_adopts_entries_written_after_the_last_head_update() {
+        let dir = scratch("stale-head");
+        let mut sink = FileAuditSink::open(&dir, "consent", 1 << 20).unwrap();
+        append_all(&mut sink, 0..2);
+        let old_head = fs::read(head_path(&dir, "consent")).unwrap();
+        append_all(&mut sink, 2..3);
+        drop(sink);
+        // Crash after the entry's sync_data, before its head update.
+        fs::write(head_path(&dir, "consent"), old_head).unwrap();
+
+        let mut sink = FileAuditSink::open(&dir, "consent", 1 << 20).unwrap();
+        assert_eq!(sink.head().next_seq, 3);
+        append_all(&mut sink, 3..4);
+        assert_eq!(verify_audit_log(&dir, "consent").unwrap().next_seq, 4);
+    }
+
+    #[test]
+    fn open_refuses_a_log_shorter_than_its_head() {
+        let dir = scratch("truncated");
+        let mut sink = FileAuditSink::open(&dir, "consent", 1 << 20).unwrap();
+        append_all(&mut sink, 0..3);
+        drop(sink);
+        let path = segment_path(&dir, "consent", 0);
+        let raw = fs::read_to_string(&path).unwrap();
+        let kept: Vec<&str> = raw.lines().take(2).collect();
+        fs::write(&path, kept.join("\n") + "\n").unwrap();
+
+        match FileAuditSink::open(&dir, "consent", 1 << 20) {
+            Err(AuditLogError::Chain(ChainViolation::Truncated {
+                expected_next_seq: 3,
+                found_next_seq: 2,
+            })) => {}
+            other => panic!("expected a truncated chain, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn verify_detects_an_edited_entry() {
+        let dir = scratch("edited");
+        let mut sink = FileAuditSink::open(&dir, "consent", 1 << 20).unwrap();
+        append_all(&mut sink, 0..3);
+        drop(sink);
+        let path = segment_path(&dir, "consent", 0);
+        let raw = fs::read_to_string(&path).unwrap();
+        fs::write(&path, raw.replace("\"Suspended\"", "\"ResumedByHost\"")).unwrap();
+
+        match verify_audit_log(&dir, "consent") {
+            Err(VerifyError::Violation(ChainViolation::HashMismatch { segment: 0, line: 1 })) => {}
+            other => panic!("expected a hash mismatch, got {:?}", other),
+        }
+    }
+
+    /// Sink that fails every append while `failing` is set.
+    #[derive(Default)]
+    struct FlakySink {
+        failing: bool,
+        inner: MemoryAuditSink,
+    }
+
+    impl AuditSink for FlakySink {
+        fn append(&mut self, event: &AuditEvent) -> Result<(), AuditLogError> {
+            if self.failing {
+                return Err(io::Error::other("disk full").into());
+            }
+            self.inner.append(event)
+        }
+    }
+
+    #[test]
+    fn suspension_records_survive_a_failed_write() {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(0.9, 0));
+        let request = pay(1_000, false, 0);
+        let mut sink = FlakySink {
+            failing: true,
+            ..FlakySink::default()
+        };
+        let failed = AugFingerprintGuard::evaluate_logged(&mut shard, &request, None, &mut sink);
+        assert!(failed.is_err());
+        assert_eq!(shard.suspension_log.len(), 1);
+
+        sink.failing = false;
+        let outcome =
+            AugFingerprintGuard::evaluate_logged(&mut shard, &request, None, &mut sink).unwrap();
+        assert_eq!(outcome.reason, ConsentReason::ConsentSuspended);
+        assert!(shard.suspension_log.is_empty());
+        let kinds: Vec<_> = sink
+            .inner
+            .events
+            .iter()
+            .map(|e| matches!(e, AuditEvent::Suspension(_)))
+            .collect();
+        assert_eq!(kinds, [true, false]);
+    }
+}
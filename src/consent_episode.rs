@@ -0,0 +1,693 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::augfingerprint_corridor::AugFingerprintCorridor;
+use crate::paycomp_augfingerprint_guard::{AugFingerprintShard, ConsentAuditRecord};
+
+/// Episodes kept for the feedback summary; older ones age out.
+pub const EPISODE_WINDOW: usize = 50;
+
+/// Most recent decision ids kept for the duplicate check; spans many
+/// `EPISODE_WINDOW`s without growing for the life of the wallet.
+pub const RECORDED_ID_WINDOW: usize = 1_000;
+
+/// Fewest episodes in the window before any adjustment is proposed.
+pub const MIN_EPISODES_FOR_PROPOSAL: u32 = 5;
+
+/// Host's label for one consent episode (compat shard `feedback_label`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackLabel {
+    Ok,
+    Borderline,
+    TooMuch,
+    FeltWrong,
+    RightsViolationSuspected,
+}
+
+/// One consent episode, tied to its `ConsentAuditRecord` by `decision_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentEpisode {
+    pub decision_id: Uuid,
+    pub wallet_did: String,
+    pub recorded_at: SystemTime,
+    pub feedback: Option<FeedbackLabel>,
+    /// Measured latency of the consent interaction (compat shard `consent_latency_ms`).
+    pub consent_latency_ms: Option<u64>,
+}
+
+/// Feedback over the episodes currently in the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct FeedbackSummary {
+    pub episodes: u32,
+    pub ok: u32,
+    pub borderline: u32,
+    pub too_much: u32,
+    pub felt_wrong: u32,
+    pub rights_violation_suspected: u32,
+    pub mean_latency_ms: Option<u64>,
+}
+
+impl FeedbackSummary {
+    /// Episodes labelled `too_much` or `felt_wrong`.
+    pub fn negative(&self) -> u32 {
+        self.too_much + self.felt_wrong
+    }
+
+    pub fn labelled(&self) -> u32 {
+        self.ok + self.borderline + self.negative() + self.rights_violation_suspected
+    }
+}
+
+/// Prompt-corridor setting an episode proposal may adjust. Proposals act on the
+/// POS's `AugFingerprintCorridor`, which sets the prompt budget and pacing.
+///
+/// `latency_ms_max` is the overload gate and is never adjusted: raising it would
+/// let longer prompts through. A slow host gets `PacingDirective::ExtendTimeout`
+/// from the measured latency instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorridorSetting {
+    MaxPromptsPerHour,
+    LatencyMsMin,
+}
+
+/// Corridor settings as of the first recorded episode. Proposals never loosen
+/// the POS corridor's prompts past its baseline, nor lower its latency floor
+/// below half of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CorridorBaseline {
+    pub max_prompts_per_hour: u32,
+    pub latency_ms_min: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorridorChange {
+    pub setting: CorridorSetting,
+    pub from: u64,
+    pub to: u64,
+}
+
+/// Adjustment waiting for the host's approval; nothing changes until then.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorridorProposal {
+    pub proposal_id: u64,
+    pub change: CorridorChange,
+    pub proposed_at: SystemTime,
+    pub basis: FeedbackSummary,
+}
+
+/// Raised on `rights_violation_suspected`; the host's advocate or caregiver
+/// must review it. The corridors were already tightened when it was raised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Escalation {
+    pub decision_id: Uuid,
+    pub wallet_did: String,
+    pub merchant_id: String,
+    pub raised_at: SystemTime,
+    /// Changes made to the POS `AugFingerprintCorridor`.
+    pub tightened: Vec<CorridorChange>,
+    /// The same tightening applied to the wallet shard's own prompt budget.
+    pub wallet_tightened: Vec<CorridorChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpisodeError {
+    /// The audit record belongs to another wallet.
+    WrongWallet { wallet_did: String },
+    /// An episode for this decision was already recorded.
+    Duplicate { decision_id: Uuid },
+    UnknownProposal { proposal_id: u64 },
+    /// The setting changed since the proposal was made; propose again.
+    StaleProposal { proposal_id: u64 },
+}
+
+impl fmt::Display for EpisodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EpisodeError::WrongWallet { wallet_did } => {
+                write!(f, "audit record belongs to wallet `{}`", wallet_did)
+            }
+            EpisodeError::Duplicate { decision_id } => {
+                write!(f, "episode for decision {} already recorded", decision_id)
+            }
+            EpisodeError::UnknownProposal { proposal_id } => {
+                write!(f, "no pending proposal {}", proposal_id)
+            }
+            EpisodeError::StaleProposal { proposal_id } => {
+                write!(
+                    f,
+                    "proposal {} no longer matches the current setting",
+                    proposal_id
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EpisodeError {}
+
+/// Per-wallet episode log, pending proposals and escalations.
+#[derive(Debug, Clone)]
+pub struct EpisodeRecorder {
+    pub episodes: VecDeque<ConsentEpisode>,
+    /// Decisions of the last `RECORDED_ID_WINDOW` episodes, including those aged
+    /// out of `episodes`, so feedback on one decision is never counted twice.
+    pub recorded: HashSet<Uuid>,
+    /// `recorded` in recording order; the oldest id is forgotten first.
+    pub recorded_order: VecDeque<Uuid>,
+    pub baseline: Option<CorridorBaseline>,
+    pub proposals: Vec<CorridorProposal>,
+    pub next_proposal_id: u64,
+    pub escalations: Vec<Escalation>,
+}
+
+impl EpisodeRecorder {
+    pub fn new() -> Self {
+        Self {
+            episodes: VecDeque::new(),
+            recorded: HashSet::new(),
+            recorded_order: VecDeque::new(),
+            baseline: None,
+            proposals: Vec::new(),
+            next_proposal_id: 1,
+            escalations: Vec::new(),
+        }
+    }
+
+    /// Remember `decision_id`; `false` if it is already remembered.
+    fn remember(&mut self, decision_id: Uuid) -> bool {
+        if !self.recorded.insert(decision_id) {
+            return false;
+        }
+        self.recorded_order.push_back(decision_id);
+        while self.recorded_order.len() > RECORDED_ID_WINDOW {
+            if let Some(oldest) = self.recorded_order.pop_front() {
+                self.recorded.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    pub fn summary(&self) -> FeedbackSummary {
+        let mut s = FeedbackSummary::default();
+        let mut latency_sum = 0u64;
+        let mut latency_n = 0u64;
+        for e in &self.episodes {
+            s.episodes += 1;
+            match e.feedback {
+                Some(FeedbackLabel::Ok) => s.ok += 1,
+                Some(FeedbackLabel::Borderline) => s.borderline += 1,
+                Some(FeedbackLabel::TooMuch) => s.too_much += 1,
+                Some(FeedbackLabel::FeltWrong) => s.felt_wrong += 1,
+                Some(FeedbackLabel::RightsViolationSuspected) => s.rights_violation_suspected += 1,
+                None => {}
+            }
+            if let Some(ms) = e.consent_latency_ms {
+                latency_sum = latency_sum.saturating_add(ms);
+                latency_n += 1;
+            }
+        }
+        s.mean_latency_ms = (latency_n > 0).then(|| latency_sum / latency_n);
+        s
+    }
+}
+
+impl Default for EpisodeRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AugFingerprintCorridor {
+    pub fn corridor_setting(&self, setting: CorridorSetting) -> u64 {
+        match setting {
+            CorridorSetting::MaxPromptsPerHour => u64::from(self.max_prompts_per_hour),
+            CorridorSetting::LatencyMsMin => self.latency_ms_min,
+        }
+    }
+
+    fn set_corridor_setting(&mut self, setting: CorridorSetting, value: u64) {
+        match setting {
+            CorridorSetting::MaxPromptsPerHour => {
+                self.max_prompts_per_hour = u32::try_from(value).unwrap_or(u32::MAX)
+            }
+            CorridorSetting::LatencyMsMin => self.latency_ms_min = value,
+        }
+    }
+}
+
+/// Both the POS corridor and the wallet shard carry the adjustable settings.
+trait AdjustableCorridor {
+    fn get(&self, setting: CorridorSetting) -> u64;
+    fn set(&mut self, setting: CorridorSetting, value: u64);
+}
+
+impl AdjustableCorridor for AugFingerprintCorridor {
+    fn get(&self, setting: CorridorSetting) -> u64 {
+        self.corridor_setting(setting)
+    }
+
+    fn set(&mut self, setting: CorridorSetting, value: u64) {
+        self.set_corridor_setting(setting, value)
+    }
+}
+
+impl AdjustableCorridor for AugFingerprintShard {
+    fn get(&self, setting: CorridorSetting) -> u64 {
+        self.corridor_setting(setting)
+    }
+
+    fn set(&mut self, setting: CorridorSetting, value: u64) {
+        self.set_corridor_setting(setting, value)
+    }
+}
+
+/// Rights-violation tightening: prompts per hour halved, never below one. The
+/// overload gate `latency_ms_max` is left alone. Returns what changed.
+fn tighten(target: &mut impl AdjustableCorridor) -> Vec<CorridorChange> {
+    let setting = CorridorSetting::MaxPromptsPerHour;
+    let from = target.get(setting);
+    let to = (from / 2).max(1);
+    if to == from {
+        return Vec::new();
+    }
+    target.set(setting, to);
+    vec![CorridorChange { setting, from, to }]
+}
+
+impl AugFingerprintShard {
+    /// The wallet's own value of `setting`; the POS corridor keeps its own.
+    pub fn corridor_setting(&self, setting: CorridorSetting) -> u64 {
+        match setting {
+            CorridorSetting::MaxPromptsPerHour => u64::from(self.max_prompts_per_hour),
+            CorridorSetting::LatencyMsMin => self.latency_ms_min,
+        }
+    }
+
+    fn set_corridor_setting(&mut self, setting: CorridorSetting, value: u64) {
+        match setting {
+            CorridorSetting::MaxPromptsPerHour => {
+                self.max_prompts_per_hour = u32::try_from(value).unwrap_or(u32::MAX)
+            }
+            CorridorSetting::LatencyMsMin => self.latency_ms_min = value,
+        }
+    }
+
+    /// Record the host's feedback and measured latency for the decision in `record`.
+    ///
+    /// `pos` is the prompt corridor the host's `AugCitizenPosGuard` reads; its
+    /// settings on the first episode become the proposal baseline.
+    /// `rights_violation_suspected` tightens `pos` and the wallet's own budget at
+    /// once (prompts per hour halved), drops pending proposals and raises an
+    /// `Escalation`; it never waits for host approval.
+    pub fn record_episode(
+        &mut self,
+        record: &ConsentAuditRecord,
+        feedback: Option<FeedbackLabel>,
+        consent_latency_ms: Option<u64>,
+        pos: &mut AugFingerprintCorridor,
+        at: SystemTime,
+    ) -> Result<(), EpisodeError> {
+        if record.wallet_did != self.wallet_did {
+            return Err(EpisodeError::WrongWallet {
+                wallet_did: record.wallet_did.clone(),
+            });
+        }
+        if !self.consent_episodes.remember(record.decision_id) {
+            return Err(EpisodeError::Duplicate {
+                decision_id: record.decision_id,
+            });
+        }
+
+        self.consent_episodes
+            .baseline
+            .get_or_insert(CorridorBaseline {
+                max_prompts_per_hour: pos.max_prompts_per_hour,
+                latency_ms_min: pos.latency_ms_min,
+            });
+        self.consent_episodes.episodes.push_back(ConsentEpisode {
+            decision_id: record.decision_id,
+            wallet_did: record.wallet_did.clone(),
+            recorded_at: at,
+            feedback,
+            consent_latency_ms,
+        });
+        while self.consent_episodes.episodes.len() > EPISODE_WINDOW {
+            self.consent_episodes.episodes.pop_front();
+        }
+
+        if feedback == Some(FeedbackLabel::RightsViolationSuspected) {
+            let tightened = tighten(pos);
+            let wallet_tightened = tighten(self);
+            self.consent_episodes.proposals.clear();
+            self.consent_episodes.escalations.push(Escalation {
+                decision_id: record.decision_id,
+                wallet_did: self.wallet_did.clone(),
+                merchant_id: record.merchant_id.clone(),
+                raised_at: at,
+                tightened,
+                wallet_tightened,
+            });
+        }
+        Ok(())
+    }
+
+    /// Replace pending proposals for `pos`, the POS prompt corridor, with ones
+    /// derived from the current feedback window.
+    ///
+    /// Each step is bounded: prompts per hour move by at most a fifth (at least one),
+    /// never below one and never above the baseline; the latency floor moves down
+    /// by at most a quarter and never below half its baseline.
+    /// - mostly `too_much` / `felt_wrong`: fewer prompts;
+    /// - mean latency under the floor: lower it, so quick answers aren't paced;
+    /// - consistently `ok` after an earlier cut: step prompts back toward baseline.
+    ///
+    /// The overload gate `latency_ms_max` is never raised; slow hosts get a longer
+    /// timeout from `AugCitizenPosGuard::pacing`.
+    ///
+    /// Nothing is proposed while a `rights_violation_suspected` episode is still in
+    /// the window; loosening waits for the escalation to age out.
+    pub fn propose_corridor_adjustments(
+        &mut self,
+        pos: &AugFingerprintCorridor,
+        now: SystemTime,
+    ) -> Vec<CorridorProposal> {
+        self.consent_episodes.proposals.clear();
+        let summary = self.consent_episodes.summary();
+        let Some(baseline) = self.consent_episodes.baseline else {
+            return Vec::new();
+        };
+        if summary.episodes < MIN_EPISODES_FOR_PROPOSAL || summary.rights_violation_suspected > 0 {
+            return Vec::new();
+        }
+
+        let mut changes = Vec::new();
+        let prompts = u64::from(pos.max_prompts_per_hour);
+        let prompt_step = (prompts / 5).max(1);
+        let labelled = summary.labelled().max(1);
+        let negative_share = f64::from(summary.negative()) / f64::from(labelled);
+        let ok_share = f64::from(summary.ok) / f64::from(labelled);
+
+        if negative_share >= 0.3 {
+            changes.push((
+                CorridorSetting::MaxPromptsPerHour,
+                prompts.saturating_sub(prompt_step).max(1),
+            ));
+        } else if ok_share >= 0.9 && prompts < u64::from(baseline.max_prompts_per_hour) {
+            let restored = (prompts + prompt_step).min(u64::from(baseline.max_prompts_per_hour));
+            changes.push((CorridorSetting::MaxPromptsPerHour, restored));
+        }
+
+        if let Some(mean) = summary.mean_latency_ms {
+            let floor = baseline.latency_ms_min / 2;
+            if mean < pos.latency_ms_min {
+                let lowered = pos.latency_ms_min - pos.latency_ms_min / 4;
+                changes.push((CorridorSetting::LatencyMsMin, lowered.max(mean).max(floor)));
+            }
+        }
+
+        for (setting, to) in changes {
+            let from = pos.corridor_setting(setting);
+            if to == from {
+                continue;
+            }
+            let proposal_id = self.consent_episodes.next_proposal_id;
+            self.consent_episodes.next_proposal_id =
+                self.consent_episodes.next_proposal_id.wrapping_add(1);
+            self.consent_episodes.proposals.push(CorridorProposal {
+                proposal_id,
+                change: CorridorChange { setting, from, to },
+                proposed_at: now,
+                basis: summary,
+            });
+        }
+        self.consent_episodes.proposals.clone()
+    }
+
+    /// Host approves a pending proposal; the setting of `pos` changes only now.
+    pub fn approve_corridor_proposal(
+        &mut self,
+        proposal_id: u64,
+        pos: &mut AugFingerprintCorridor,
+    ) -> Result<CorridorChange, EpisodeError> {
+        let proposal = self.take_corridor_proposal(proposal_id)?;
+        let change = proposal.change;
+        if pos.corridor_setting(change.setting) != change.from {
+            return Err(EpisodeError::StaleProposal { proposal_id });
+        }
+        pos.set_corridor_setting(change.setting, change.to);
+        Ok(change)
+    }
+
+    pub fn reject_corridor_proposal(&mut self, proposal_id: u64) -> Result<(), EpisodeError> {
+        self.take_corridor_proposal(proposal_id).map(|_| ())
+    }
+
+    /// Hand raised escalations to the host's reporting channel.
+    pub fn drain_escalations(&mut self) -> Vec<Escalation> {
+        std::mem::take(&mut self.consent_episodes.escalations)
+    }
+
+    fn take_corridor_proposal(
+        &mut self,
+        proposal_id: u64,
+    ) -> Result<CorridorProposal, EpisodeError> {
+        let idx = self
+            .consent_episodes
+            .proposals
+            .iter()
+            .position(|p| p.proposal_id == proposal_id)
+            .ok_or(EpisodeError::UnknownProposal { proposal_id })?;
+        Ok(self.consent_episodes.proposals.remove(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::augfingerprint_corridor::PromptStakes;
+    use crate::paycomp_au_guard::{AugCitizenPosGuard, PromptDecision, PromptState};
+    use crate::paycomp_augfingerprint_guard::AugFingerprintGuard;
+    use crate::test_support::{at, pay, pos_corridor, shard};
+
+    /// POS corridor deliberately tighter than the wallet shard's defaults
+    /// (10 prompts per hour, 500–3_000 ms).
+    fn pos() -> AugFingerprintCorridor {
+        AugFingerprintCorridor {
+            latency_ms_min: 600,
+            latency_ms_max: 2_000,
+            max_prompts_per_hour: 8,
+            max_informational_per_hour: 8,
+            max_low_value_payments_per_hour: 8,
+            ..pos_corridor()
+        }
+    }
+
+    /// Shard plus an audit record of one of its decisions.
+    fn shard_and_record() -> (AugFingerprintShard, ConsentAuditRecord) {
+        let mut shard = shard();
+        let (_, _, audit) = AugFingerprintGuard::evaluate(&mut shard, &pay(1_000, false, 0), None);
+        (shard, audit.unwrap())
+    }
+
+    /// `record` under a fresh decision id.
+    fn another(record: &ConsentAuditRecord) -> ConsentAuditRecord {
+        ConsentAuditRecord {
+            decision_id: Uuid::new_v4(),
+            ..record.clone()
+        }
+    }
+
+    #[test]
+    fn rights_violation_tightens_the_pos_corridor_and_the_wallet_at_once() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        let violation = Some(FeedbackLabel::RightsViolationSuspected);
+        shard.record_episode(&record, violation, None, &mut pos, at(1)).unwrap();
+
+        assert_eq!(pos.max_prompts_per_hour, 4);
+        assert_eq!(shard.max_prompts_per_hour, 5);
+        // The overload gates are never loosened.
+        assert_eq!(pos.latency_ms_max, 2_000);
+        assert_eq!(shard.latency_ms_max, 3_000);
+
+        let escalations = shard.drain_escalations();
+        assert_eq!(escalations.len(), 1);
+        let prompts = CorridorSetting::MaxPromptsPerHour;
+        assert_eq!(
+            escalations[0].tightened,
+            vec![CorridorChange {
+                setting: prompts,
+                from: 8,
+                to: 4,
+            }]
+        );
+        assert_eq!(
+            escalations[0].wallet_tightened,
+            vec![CorridorChange {
+                setting: prompts,
+                from: 10,
+                to: 5,
+            }]
+        );
+
+        // The POS guard reading the corridor now stops after four prompts.
+        let mut guard = AugCitizenPosGuard::new(&pos, PromptState::new());
+        for _ in 0..4 {
+            guard.record_prompt(PromptStakes::Informational, at(2));
+        }
+        let decision = guard.should_prompt_at(PromptStakes::Informational, 1_000, at(2));
+        assert!(matches!(decision, PromptDecision::DeferSoft { .. }));
+    }
+
+    #[test]
+    fn repeated_violations_halve_prompts_down_to_one() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        let violation = Some(FeedbackLabel::RightsViolationSuspected);
+        for t in 1..=4 {
+            shard.record_episode(&another(&record), violation, None, &mut pos, at(t)).unwrap();
+        }
+        assert_eq!(pos.max_prompts_per_hour, 1);
+        assert_eq!(shard.max_prompts_per_hour, 1);
+        assert_eq!(pos.latency_ms_max, 2_000);
+        // The last violation had nothing left to tighten.
+        let escalations = shard.drain_escalations();
+        assert!(escalations[3].tightened.is_empty());
+    }
+
+    #[test]
+    fn a_decision_is_only_recorded_once_even_after_it_ages_out() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        shard.record_episode(&record, None, None, &mut pos, at(1)).unwrap();
+        for t in 0..EPISODE_WINDOW as u64 {
+            shard.record_episode(&another(&record), None, None, &mut pos, at(2 + t)).unwrap();
+        }
+        assert_eq!(shard.consent_episodes.episodes.len(), EPISODE_WINDOW);
+        assert!(shard
+            .consent_episodes
+            .episodes
+            .iter()
+            .all(|e| e.decision_id != record.decision_id));
+
+        let ok = Some(FeedbackLabel::Ok);
+        assert_eq!(
+            shard.record_episode(&record, ok, None, &mut pos, at(100)),
+            Err(EpisodeError::Duplicate {
+                decision_id: record.decision_id
+            })
+        );
+    }
+
+    #[test]
+    fn only_the_latest_decision_ids_are_remembered() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        shard.record_episode(&record, None, None, &mut pos, at(1)).unwrap();
+        for t in 0..RECORDED_ID_WINDOW as u64 {
+            shard.record_episode(&another(&record), None, None, &mut pos, at(2 + t)).unwrap();
+        }
+        let episodes = &shard.consent_episodes;
+        assert_eq!(episodes.recorded.len(), RECORDED_ID_WINDOW);
+        assert_eq!(episodes.recorded_order.len(), RECORDED_ID_WINDOW);
+        assert!(!episodes.recorded.contains(&record.decision_id));
+    }
+
+    #[test]
+    fn records_from_another_wallet_are_rejected() {
+        let (mut shard, record) = shard_and_record();
+        let foreign = ConsentAuditRecord {
+            wallet_did: "did:aln:other".into(),
+            ..record
+        };
+        assert_eq!(
+            shard.record_episode(&foreign, None, None, &mut pos(), at(1)),
+            Err(EpisodeError::WrongWallet {
+                wallet_did: "did:aln:other".into()
+            })
+        );
+    }
+
+    #[test]
+    fn negative_feedback_proposes_a_tighter_pos_corridor_for_approval() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        assert!(shard.propose_corridor_adjustments(&pos, at(0)).is_empty());
+        for t in 1..=MIN_EPISODES_FOR_PROPOSAL as u64 {
+            let too_much = Some(FeedbackLabel::TooMuch);
+            let episode = another(&record);
+            shard.record_episode(&episode, too_much, Some(300), &mut pos, at(t)).unwrap();
+        }
+
+        let proposals = shard.propose_corridor_adjustments(&pos, at(10));
+        let changes: Vec<_> = proposals.iter().map(|p| p.change).collect();
+        assert_eq!(
+            changes,
+            vec![
+                CorridorChange {
+                    setting: CorridorSetting::MaxPromptsPerHour,
+                    from: 8,
+                    to: 7,
+                },
+                CorridorChange {
+                    setting: CorridorSetting::LatencyMsMin,
+                    from: 600,
+                    to: 450,
+                },
+            ]
+        );
+        // Nothing changes before the host approves.
+        assert_eq!(pos.max_prompts_per_hour, 8);
+
+        let prompts = proposals[0].proposal_id;
+        assert_eq!(shard.approve_corridor_proposal(prompts, &mut pos), Ok(changes[0]));
+        assert_eq!(pos.max_prompts_per_hour, 7);
+        assert_eq!(shard.max_prompts_per_hour, 10);
+        assert_eq!(
+            shard.approve_corridor_proposal(prompts, &mut pos),
+            Err(EpisodeError::UnknownProposal { proposal_id: prompts })
+        );
+
+        let latency = proposals[1].proposal_id;
+        pos.latency_ms_min = 550;
+        assert_eq!(
+            shard.approve_corridor_proposal(latency, &mut pos),
+            Err(EpisodeError::StaleProposal { proposal_id: latency })
+        );
+        assert_eq!(pos.latency_ms_min, 550);
+    }
+
+    #[test]
+    fn slow_answers_never_raise_the_overload_gate() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        for t in 1..=MIN_EPISODES_FOR_PROPOSAL as u64 {
+            let felt_wrong = Some(FeedbackLabel::FeltWrong);
+            let episode = another(&record);
+            shard.record_episode(&episode, felt_wrong, Some(1_950), &mut pos, at(t)).unwrap();
+        }
+        let proposals = shard.propose_corridor_adjustments(&pos, at(10));
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].change.setting, CorridorSetting::MaxPromptsPerHour);
+        assert!(proposals[0].change.to < proposals[0].change.from);
+    }
+
+    #[test]
+    fn nothing_is_proposed_while_a_violation_is_in_the_window() {
+        let (mut shard, record) = shard_and_record();
+        let mut pos = pos();
+        let violation = Some(FeedbackLabel::RightsViolationSuspected);
+        shard.record_episode(&record, violation, None, &mut pos, at(1)).unwrap();
+        for t in 2..10 {
+            let ok = Some(FeedbackLabel::Ok);
+            shard.record_episode(&another(&record), ok, Some(1_000), &mut pos, at(t)).unwrap();
+        }
+        assert!(shard.propose_corridor_adjustments(&pos, at(10)).is_empty());
+    }
+}
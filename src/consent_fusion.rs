@@ -0,0 +1,337 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentState, AugFingerprintGuard, AugFingerprintShard, ConsentOutcome, ControlMode,
+    PaymentRequest,
+};
+
+/// Input channel a consent signal arrived on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentChannel {
+    /// BCI / internal biophysical consent state from the AI-companion.
+    BciState,
+    ExternalSwitch,
+    Voice,
+    /// Visual confirm in the XR / oculus flow.
+    XrVisual,
+}
+
+impl ConsentChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConsentChannel::BciState => "bci_state",
+            ConsentChannel::ExternalSwitch => "external_switch",
+            ConsentChannel::Voice => "voice",
+            ConsentChannel::XrVisual => "xr_visual",
+        }
+    }
+}
+
+/// One reading from one channel.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChannelSignal {
+    pub channel: ConsentChannel,
+    /// `true` for a confirm, `false` for an explicit refusal.
+    pub confirm: bool,
+    /// Channel-reported certainty in [0, 1].
+    pub confidence: f32,
+    pub observed_at: SystemTime,
+}
+
+/// Reliability weight per channel, each in [0, 1]. A zero weight disables the channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelWeights {
+    pub bci_state: f32,
+    pub external_switch: f32,
+    pub voice: f32,
+    pub xr_visual: f32,
+}
+
+impl ChannelWeights {
+    pub const DEFAULT: Self = Self {
+        bci_state: 1.0,
+        external_switch: 1.0,
+        voice: 0.6,
+        xr_visual: 0.8,
+    };
+
+    pub fn weight(&self, channel: ConsentChannel) -> f32 {
+        match channel {
+            ConsentChannel::BciState => self.bci_state,
+            ConsentChannel::ExternalSwitch => self.external_switch,
+            ConsentChannel::Voice => self.voice,
+            ConsentChannel::XrVisual => self.xr_visual,
+        }
+    }
+
+    /// Sum of the weights of every enabled channel.
+    pub fn enabled_total(&self) -> f32 {
+        [self.bci_state, self.external_switch, self.voice, self.xr_visual]
+            .iter()
+            .filter(|w| **w > 0.0)
+            .sum()
+    }
+}
+
+impl Default for ChannelWeights {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Why a signal did not contribute to the fused confidence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignalIgnored {
+    /// The channel's effective weight is zero (e.g. `speech_reliable = false`).
+    ChannelDisabled,
+    /// Older than the shard's `neuro_state_max_age` at decision time.
+    Stale,
+    /// A newer reading from the same channel was used instead.
+    Superseded,
+}
+
+/// Per-channel evidence kept with the audit record.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChannelEvidence {
+    pub signal: ChannelSignal,
+    pub weight: f32,
+    /// Signed weighted contribution (negative for refusals); zero when ignored.
+    pub contribution: f32,
+    pub ignored: Option<SignalIgnored>,
+}
+
+/// Result of fusing all channel signals for one decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FusedConsent {
+    /// Weighted confirm confidence in [0, 1]; refusals pull it down.
+    pub confidence: f32,
+    /// An enabled channel explicitly refused.
+    pub refused: bool,
+    pub evidence: Vec<ChannelEvidence>,
+}
+
+impl FusedConsent {
+    /// Macro-state handed to the guard: only a confident, unrefused fusion confirms.
+    pub fn ai_consent_state(&self, min_confidence: f32) -> AiConsentState {
+        if self.refused {
+            AiConsentState::Deny
+        } else if self.confidence >= min_confidence {
+            AiConsentState::Confirmed
+        } else {
+            AiConsentState::Unknown
+        }
+    }
+}
+
+impl AugFingerprintShard {
+    /// Channel weights after applying the host's profile: an unreliable speech
+    /// channel and an uncalibrated oculus-cortex link never count.
+    pub fn effective_channel_weights(&self) -> ChannelWeights {
+        let mut w = self.channel_weights;
+        if !self.speech_reliable {
+            w.voice = 0.0;
+        }
+        if !self.oculus_cortex_calibrated {
+            w.xr_visual = 0.0;
+        }
+        w
+    }
+
+    /// Fuse `signals` at `now` into one confidence, using the latest fresh reading
+    /// of every enabled channel.
+    ///
+    /// The weighted contributions are divided by the total weight of every enabled
+    /// channel, reporting or not: a silent channel counts as no confirmation, and a
+    /// lone low-weight channel can't reach full confidence on its own.
+    pub fn fuse_consent(&self, signals: &[ChannelSignal], now: SystemTime) -> FusedConsent {
+        let weights = self.effective_channel_weights();
+        let mut evidence: Vec<ChannelEvidence> = Vec::with_capacity(signals.len());
+
+        for (i, signal) in signals.iter().enumerate() {
+            let weight = weights.weight(signal.channel);
+            let superseded = signals[i + 1..]
+                .iter()
+                .any(|s| s.channel == signal.channel && s.observed_at >= signal.observed_at);
+            let stale = now
+                .duration_since(signal.observed_at)
+                .is_ok_and(|age| age > self.neuro_state_max_age);
+            let ignored = if weight <= 0.0 {
+                Some(SignalIgnored::ChannelDisabled)
+            } else if stale {
+                Some(SignalIgnored::Stale)
+            } else if superseded {
+                Some(SignalIgnored::Superseded)
+            } else {
+                None
+            };
+            let contribution = match ignored {
+                Some(_) => 0.0,
+                None => {
+                    let c = weight * signal.confidence.clamp(0.0, 1.0);
+                    if signal.confirm {
+                        c
+                    } else {
+                        -c
+                    }
+                }
+            };
+            evidence.push(ChannelEvidence {
+                signal: *signal,
+                weight,
+                contribution,
+                ignored,
+            });
+        }
+
+        let used = evidence.iter().filter(|e| e.ignored.is_none());
+        let total_weight = weights.enabled_total();
+        let sum: f32 = used.clone().map(|e| e.contribution).sum();
+        let refused = used.clone().any(|e| !e.signal.confirm && e.contribution < 0.0);
+        let confidence = if total_weight > 0.0 {
+            (sum / total_weight).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        FusedConsent {
+            confidence,
+            refused,
+            evidence,
+        }
+    }
+}
+
+impl AugFingerprintGuard {
+    /// Entry point for `ControlMode::Mixed`: fuse every channel's signal, then run the
+    /// regular evaluation with the fused macro-state. The fused confidence and the
+    /// per-channel evidence are attached to the audit record.
+    ///
+    /// A wallet in any other control mode never takes fused signals as consent:
+    /// it is evaluated with an `Unknown` macro-state.
+    pub fn evaluate_mixed(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        signals: &[ChannelSignal],
+    ) -> ConsentOutcome {
+        if shard.control_mode != ControlMode::Mixed {
+            return Self::evaluate_outcome(shard, request, Some(AiConsentState::Unknown));
+        }
+        let fused = shard.fuse_consent(signals, request.now);
+        let ai_state = fused.ai_consent_state(shard.fusion_min_confidence);
+        let mut outcome = Self::evaluate_outcome(shard, request, Some(ai_state));
+        if let Some(record) = outcome.audit.as_mut() {
+            record.fusion = Some(fused);
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::{ConsentDecision, ConsentReason};
+    use crate::test_support::{at, pay, shard, steady};
+
+    fn signal(channel: ConsentChannel, confirm: bool, confidence: f32, t: u64) -> ChannelSignal {
+        ChannelSignal {
+            channel,
+            confirm,
+            confidence,
+            observed_at: at(t),
+        }
+    }
+
+    /// Mixed-mode host with a reliable voice channel, steady in corridor at t=20.
+    fn mixed_shard() -> AugFingerprintShard {
+        let mut shard = shard();
+        shard.control_mode = ControlMode::Mixed;
+        shard.speech_reliable = true;
+        steady(&mut shard, 20);
+        shard
+    }
+
+    #[test]
+    fn a_lone_low_weight_channel_cannot_confirm() {
+        let mut shard = mixed_shard();
+        shard.channel_weights.voice = 0.05;
+        let fused = shard.fuse_consent(&[signal(ConsentChannel::Voice, true, 1.0, 20)], at(20));
+        // 0.05 out of 1.0 + 1.0 + 0.05 enabled.
+        assert!((fused.confidence - 0.05 / 2.05).abs() < 1e-6);
+        assert_eq!(fused.ai_consent_state(shard.fusion_min_confidence), AiConsentState::Unknown);
+    }
+
+    #[test]
+    fn confident_channels_together_confirm() {
+        let shard = mixed_shard();
+        let fused = shard.fuse_consent(
+            &[
+                signal(ConsentChannel::BciState, true, 0.9, 19),
+                signal(ConsentChannel::ExternalSwitch, true, 1.0, 20),
+                signal(ConsentChannel::Voice, true, 0.5, 20),
+            ],
+            at(20),
+        );
+        // (0.9 + 1.0 + 0.6 * 0.5) / (1.0 + 1.0 + 0.6)
+        assert!((fused.confidence - 2.2 / 2.6).abs() < 1e-6);
+        assert_eq!(fused.ai_consent_state(shard.fusion_min_confidence), AiConsentState::Confirmed);
+    }
+
+    #[test]
+    fn refusal_wins_and_ignored_signals_are_explained() {
+        let shard = mixed_shard();
+        let fused = shard.fuse_consent(
+            &[
+                signal(ConsentChannel::BciState, true, 1.0, 0),
+                signal(ConsentChannel::XrVisual, true, 1.0, 20),
+                signal(ConsentChannel::ExternalSwitch, true, 1.0, 15),
+                signal(ConsentChannel::ExternalSwitch, false, 0.8, 20),
+            ],
+            at(40),
+        );
+        let ignored: Vec<_> = fused.evidence.iter().map(|e| e.ignored).collect();
+        assert_eq!(
+            ignored,
+            [
+                Some(SignalIgnored::Stale),
+                Some(SignalIgnored::ChannelDisabled),
+                Some(SignalIgnored::Superseded),
+                None,
+            ]
+        );
+        assert!(fused.refused);
+        assert_eq!(fused.confidence, 0.0);
+        assert_eq!(fused.ai_consent_state(0.0), AiConsentState::Deny);
+    }
+
+    #[test]
+    fn evaluate_mixed_attaches_the_fusion_to_the_audit_record() {
+        let mut shard = mixed_shard();
+        let signals = [
+            signal(ConsentChannel::BciState, true, 1.0, 20),
+            signal(ConsentChannel::ExternalSwitch, true, 1.0, 20),
+        ];
+        let outcome = AugFingerprintGuard::evaluate_mixed(&mut shard, &pay(1_000, false, 20), &signals);
+        assert_eq!(outcome.decision, ConsentDecision::Allow);
+        let fusion = outcome.audit.unwrap().fusion.unwrap();
+        assert_eq!(fusion.evidence.len(), 2);
+    }
+
+    #[test]
+    fn evaluate_mixed_outside_mixed_mode_never_confirms() {
+        let mut shard = mixed_shard();
+        shard.control_mode = ControlMode::InternalBiophysical;
+        let signals = [
+            signal(ConsentChannel::BciState, true, 1.0, 20),
+            signal(ConsentChannel::ExternalSwitch, true, 1.0, 20),
+        ];
+        let outcome = AugFingerprintGuard::evaluate_mixed(&mut shard, &pay(1_000, false, 20), &signals);
+        assert_eq!(
+            (outcome.decision, outcome.reason),
+            (ConsentDecision::Deny, ConsentReason::AiConsentNotConfirmed)
+        );
+        assert!(outcome.audit.unwrap().fusion.is_none());
+    }
+}
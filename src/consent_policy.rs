@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentPolicy, AiConsentState, AugFingerprintShard, ConsentDecision, ConsentReason,
+    PaymentRequest,
+};
+
+/// Per-risk ceilings and stability time a consent policy applies.
+///
+/// Loaded from the ALN shard (`rprivacymax`, `rfraudmax`, `rtrackingmax`,
+/// `minstabilitytimems`); fields that are absent keep the policy's defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolicyThresholds {
+    pub r_privacy_max: f32,
+    pub r_fraud_max: f32,
+    pub r_tracking_max: f32,
+    pub min_stability_time: Duration,
+}
+
+impl PolicyThresholds {
+    pub const CONSERVATIVE: Self = Self {
+        r_privacy_max: 0.5,
+        r_fraud_max: 0.5,
+        r_tracking_max: 0.5,
+        min_stability_time: Duration::from_secs(5),
+    };
+
+    pub const BALANCED: Self = Self {
+        r_privacy_max: 0.7,
+        r_fraud_max: 0.7,
+        r_tracking_max: 0.7,
+        min_stability_time: Duration::from_secs(3),
+    };
+
+    pub const CAREGIVER_COAPPROVAL: Self = Self::CONSERVATIVE;
+
+    /// Whether any residual risk on the shard is above its ceiling.
+    pub fn exceeded_by(&self, shard: &AugFingerprintShard) -> bool {
+        shard.r_privacy > self.r_privacy_max
+            || shard.r_fraud > self.r_fraud_max
+            || shard.r_tracking > self.r_tracking_max
+    }
+}
+
+impl AiConsentPolicy {
+    /// Thresholds used when the shard doesn't override them.
+    pub fn default_thresholds(self) -> PolicyThresholds {
+        match self {
+            AiConsentPolicy::Conservative => PolicyThresholds::CONSERVATIVE,
+            AiConsentPolicy::Balanced => PolicyThresholds::BALANCED,
+            AiConsentPolicy::CaregiverCoapproval => PolicyThresholds::CAREGIVER_COAPPROVAL,
+        }
+    }
+}
+
+/// What a policy sees once the rights-level checks have passed.
+///
+/// Suspension, rate and spend caps, freshness and the S_t / L_t corridor are
+/// enforced by `AugFingerprintGuard` before any policy runs and can't be relaxed.
+pub struct PolicyContext<'a> {
+    pub shard: &'a AugFingerprintShard,
+    pub request: &'a PaymentRequest,
+    pub ai_state: Option<AiConsentState>,
+}
+
+/// Pluggable consent policy called by `AugFingerprintGuard`.
+pub trait ConsentPolicy {
+    /// Stable name for audit and diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Dwell time S_t / L_t must have spent in corridor before this policy runs.
+    fn min_stability_time(&self) -> Duration;
+
+    /// Final decision for a request that passed the rights-level checks.
+    fn assess(&self, ctx: &PolicyContext<'_>) -> (ConsentDecision, ConsentReason);
+}
+
+/// Deny or defer (essential services) when the companion hasn't confirmed.
+/// Never guesses: only `Confirmed` passes.
+fn require_ai_confirmation(ctx: &PolicyContext<'_>) -> Option<(ConsentDecision, ConsentReason)> {
+    match ctx.ai_state {
+        Some(state) if state != AiConsentState::Confirmed => {
+            if ctx.request.is_essential_service {
+                Some((ConsentDecision::Defer, ConsentReason::AiConsentNotConfirmed))
+            } else {
+                Some((ConsentDecision::Deny, ConsentReason::AiConsentNotConfirmed))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Denies any payment, essential or not, while a residual risk is over its ceiling.
+#[derive(Debug, Clone, Copy)]
+pub struct Conservative {
+    pub thresholds: PolicyThresholds,
+}
+
+impl ConsentPolicy for Conservative {
+    fn name(&self) -> &'static str {
+        "conservative"
+    }
+
+    fn min_stability_time(&self) -> Duration {
+        self.thresholds.min_stability_time
+    }
+
+    fn assess(&self, ctx: &PolicyContext<'_>) -> (ConsentDecision, ConsentReason) {
+        if let Some(verdict) = require_ai_confirmation(ctx) {
+            return verdict;
+        }
+        if self.thresholds.exceeded_by(ctx.shard) {
+            return (ConsentDecision::Deny, ConsentReason::RiskScoresTooHigh);
+        }
+        (ConsentDecision::Allow, ConsentReason::Ok)
+    }
+}
+
+/// Higher default ceilings; over-ceiling essential payments are deferred, not denied.
+#[derive(Debug, Clone, Copy)]
+pub struct Balanced {
+    pub thresholds: PolicyThresholds,
+}
+
+impl ConsentPolicy for Balanced {
+    fn name(&self) -> &'static str {
+        "balanced"
+    }
+
+    fn min_stability_time(&self) -> Duration {
+        self.thresholds.min_stability_time
+    }
+
+    fn assess(&self, ctx: &PolicyContext<'_>) -> (ConsentDecision, ConsentReason) {
+        if let Some(verdict) = require_ai_confirmation(ctx) {
+            return verdict;
+        }
+        if self.thresholds.exceeded_by(ctx.shard) {
+            if ctx.request.is_essential_service {
+                return (ConsentDecision::Defer, ConsentReason::RiskScoresTooHigh);
+            } else {
+                return (ConsentDecision::Deny, ConsentReason::RiskScoresTooHigh);
+            }
+        }
+        (ConsentDecision::Allow, ConsentReason::Ok)
+    }
+}
+
+/// Conservative checks, plus a caregiver co-signs every non-essential payment and
+/// every payment above `coapproval_threshold_mills`.
+///
+/// Without an active caregiver, non-essential payments are denied and over-threshold
+/// essential ones deferred; essential payments under the threshold never wait.
+#[derive(Debug, Clone, Copy)]
+pub struct CaregiverCoapproval {
+    pub thresholds: PolicyThresholds,
+}
+
+impl ConsentPolicy for CaregiverCoapproval {
+    fn name(&self) -> &'static str {
+        "caregiver_coapproval"
+    }
+
+    fn min_stability_time(&self) -> Duration {
+        self.thresholds.min_stability_time
+    }
+
+    fn assess(&self, ctx: &PolicyContext<'_>) -> (ConsentDecision, ConsentReason) {
+        if let Some(verdict) = require_ai_confirmation(ctx) {
+            return verdict;
+        }
+        if self.thresholds.exceeded_by(ctx.shard) {
+            return (ConsentDecision::Deny, ConsentReason::RiskScoresTooHigh);
+        }
+        let essential = ctx.request.is_essential_service;
+        if essential && ctx.request.amount_mills <= ctx.shard.coapproval_threshold_mills {
+            return (ConsentDecision::Allow, ConsentReason::Ok);
+        }
+        if ctx.shard.active_caregiver().is_some() {
+            (ConsentDecision::Defer, ConsentReason::CaregiverApprovalRequired)
+        } else if essential {
+            (ConsentDecision::Defer, ConsentReason::CaregiverUnavailable)
+        } else {
+            (ConsentDecision::Deny, ConsentReason::CaregiverUnavailable)
+        }
+    }
+}
+
+impl AugFingerprintShard {
+    /// Built-in policy selected by `ai_consent_policy`, with the shard's thresholds.
+    pub fn consent_policy(&self) -> Box<dyn ConsentPolicy> {
+        let thresholds = self.policy_thresholds;
+        match self.ai_consent_policy {
+            AiConsentPolicy::Conservative => Box::new(Conservative { thresholds }),
+            AiConsentPolicy::Balanced => Box::new(Balanced { thresholds }),
+            AiConsentPolicy::CaregiverCoapproval => Box::new(CaregiverCoapproval { thresholds }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at, pay, shard};
+
+    fn assess(
+        policy: &dyn ConsentPolicy,
+        shard: &AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+    ) -> (ConsentDecision, ConsentReason) {
+        policy.assess(&PolicyContext {
+            shard,
+            request,
+            ai_state,
+        })
+    }
+
+    #[test]
+    fn only_a_confirmed_companion_state_passes() {
+        let shard = shard();
+        let policy = shard.consent_policy();
+        for state in [AiConsentState::Deny, AiConsentState::Suspended, AiConsentState::Unknown] {
+            assert_eq!(
+                assess(policy.as_ref(), &shard, &pay(1_000, false, 0), Some(state)),
+                (ConsentDecision::Deny, ConsentReason::AiConsentNotConfirmed)
+            );
+            assert_eq!(
+                assess(policy.as_ref(), &shard, &pay(1_000, true, 0), Some(state)),
+                (ConsentDecision::Defer, ConsentReason::AiConsentNotConfirmed)
+            );
+        }
+        let confirmed = Some(AiConsentState::Confirmed);
+        let allowed = assess(policy.as_ref(), &shard, &pay(1_000, false, 0), confirmed);
+        assert_eq!(allowed.0, ConsentDecision::Allow);
+        // No macro-state channel at all: the rights-level checks already gated consent.
+        let allowed = assess(policy.as_ref(), &shard, &pay(1_000, false, 0), None);
+        assert_eq!(allowed.0, ConsentDecision::Allow);
+    }
+
+    #[test]
+    fn conservative_denies_and_balanced_defers_essential_over_risk() {
+        let mut shard = shard();
+        shard.r_fraud = 0.6;
+        let essential = pay(1_000, true, 0);
+
+        shard.ai_consent_policy = AiConsentPolicy::Conservative;
+        shard.policy_thresholds = PolicyThresholds::CONSERVATIVE;
+        assert_eq!(
+            assess(shard.consent_policy().as_ref(), &shard, &essential, None),
+            (ConsentDecision::Deny, ConsentReason::RiskScoresTooHigh)
+        );
+
+        // 0.6 is under Balanced's 0.7 ceiling.
+        shard.ai_consent_policy = AiConsentPolicy::Balanced;
+        shard.policy_thresholds = PolicyThresholds::BALANCED;
+        let allowed = assess(shard.consent_policy().as_ref(), &shard, &essential, None);
+        assert_eq!(allowed.0, ConsentDecision::Allow);
+        shard.r_fraud = 0.8;
+        assert_eq!(
+            assess(shard.consent_policy().as_ref(), &shard, &essential, None),
+            (ConsentDecision::Defer, ConsentReason::RiskScoresTooHigh)
+        );
+        assert_eq!(
+            assess(shard.consent_policy().as_ref(), &shard, &pay(1_000, false, 0), None),
+            (ConsentDecision::Deny, ConsentReason::RiskScoresTooHigh)
+        );
+    }
+
+    #[test]
+    fn caregiver_policy_routes_to_the_caregiver_or_refuses_without_one() {
+        let mut shard = shard();
+        shard.ai_consent_policy = AiConsentPolicy::CaregiverCoapproval;
+        let policy = shard.consent_policy();
+        assert_eq!(policy.name(), "caregiver_coapproval");
+        let small_essential = pay(1_000, true, 0);
+        let large_essential = pay(shard.coapproval_threshold_mills + 1, true, 0);
+
+        let allowed = assess(policy.as_ref(), &shard, &small_essential, None);
+        assert_eq!(allowed.0, ConsentDecision::Allow);
+        assert_eq!(
+            assess(policy.as_ref(), &shard, &large_essential, None),
+            (ConsentDecision::Defer, ConsentReason::CaregiverUnavailable)
+        );
+        assert_eq!(
+            assess(policy.as_ref(), &shard, &pay(1_000, false, 0), None),
+            (ConsentDecision::Deny, ConsentReason::CaregiverUnavailable)
+        );
+
+        shard.appoint_caregiver("did:aln:caregiver".into(), at(0));
+        assert_eq!(
+            assess(policy.as_ref(), &shard, &pay(1_000, false, 0), None),
+            (ConsentDecision::Defer, ConsentReason::CaregiverApprovalRequired)
+        );
+    }
+
+    #[test]
+    fn shard_thresholds_override_the_policy_defaults() {
+        let mut shard = shard();
+        assert_eq!(shard.consent_policy().min_stability_time(), Duration::from_secs(5));
+        shard.ai_consent_policy = AiConsentPolicy::Balanced;
+        shard.policy_thresholds = AiConsentPolicy::Balanced.default_thresholds();
+        assert_eq!(shard.consent_policy().min_stability_time(), Duration::from_secs(3));
+
+        shard.policy_thresholds.r_privacy_max = 0.1;
+        assert!(shard.policy_thresholds.exceeded_by(&shard));
+        assert_eq!(
+            assess(shard.consent_policy().as_ref(), &shard, &pay(1_000, false, 0), None).1,
+            ConsentReason::RiskScoresTooHigh
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::paycomp_augfingerprint_guard::AugFingerprintShard;
+
+/// Suspension lifecycle of a wallet's non-essential consent.
+///
+/// `Active -> Suspended` when the guard sees the neuro state outside its corridor.
+/// `Suspended -> Recovering` once S_t / L_t are back inside the corridor.
+/// `Recovering -> Active` once the companion's samples show the state stayed
+/// inside for `recovery_cooldown`, or at any point when the host explicitly resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuspensionState {
+    Active,
+    Suspended {
+        since: SystemTime,
+    },
+    Recovering {
+        suspended_since: SystemTime,
+        in_corridor_since: SystemTime,
+    },
+}
+
+/// Transition recorded in the suspension audit trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuspensionTransition {
+    Suspended,
+    RecoveryStarted,
+    RecoveryInterrupted,
+    ResumedAfterCooldown,
+    ResumedByHost,
+}
+
+/// Audit record for a suspension state change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspensionAuditRecord {
+    pub wallet_did: String,
+    pub transition: SuspensionTransition,
+    pub from: SuspensionState,
+    pub to: SuspensionState,
+    pub timestamp: SystemTime,
+}
+
+/// Default time the neuro state must stay in corridor before consent resumes.
+pub const DEFAULT_RECOVERY_COOLDOWN: Duration = Duration::from_secs(60);
+
+impl AugFingerprintShard {
+    /// True while non-essential payments are blocked (suspended or still recovering).
+    pub fn consent_suspended(&self) -> bool {
+        !matches!(self.suspension, SuspensionState::Active)
+    }
+
+    /// Suspend non-essential consent (called by the guard when the corridor is left).
+    pub fn suspend_consent(&mut self, now: SystemTime) {
+        match self.suspension {
+            SuspensionState::Active => {
+                self.transition(
+                    SuspensionState::Suspended { since: now },
+                    SuspensionTransition::Suspended,
+                    now,
+                );
+            }
+            SuspensionState::Recovering {
+                suspended_since, ..
+            } => {
+                self.transition(
+                    SuspensionState::Suspended {
+                        since: suspended_since,
+                    },
+                    SuspensionTransition::RecoveryInterrupted,
+                    now,
+                );
+            }
+            SuspensionState::Suspended { .. } => {}
+        }
+    }
+
+    /// Explicit host resume; always honoured, whatever the current neuro state.
+    pub fn host_resume(&mut self, now: SystemTime) {
+        if self.consent_suspended() {
+            self.transition(
+                SuspensionState::Active,
+                SuspensionTransition::ResumedByHost,
+                now,
+            );
+        }
+    }
+
+    /// Advance the recovery state machine from the current neuro state at `now`.
+    ///
+    /// Called on every neuro-state update and before each consent evaluation. Only
+    /// the companion's samples vouch for the corridor: recovery starts at the sample
+    /// that showed S_t / L_t back inside, and completes once a later sample lands at
+    /// least `recovery_cooldown` after it. Silence never completes a cooldown.
+    pub fn poll_recovery(&mut self, now: SystemTime) {
+        let in_corridor = self.within_corridor();
+        let observed = self.stability.last_observed().unwrap_or(now);
+        match self.suspension {
+            SuspensionState::Active => {}
+            SuspensionState::Suspended { since } => {
+                if in_corridor {
+                    self.transition(
+                        SuspensionState::Recovering {
+                            suspended_since: since,
+                            in_corridor_since: observed.max(since),
+                        },
+                        SuspensionTransition::RecoveryStarted,
+                        now,
+                    );
+                }
+            }
+            SuspensionState::Recovering {
+                suspended_since,
+                in_corridor_since,
+            } => {
+                if !in_corridor {
+                    self.transition(
+                        SuspensionState::Suspended {
+                            since: suspended_since,
+                        },
+                        SuspensionTransition::RecoveryInterrupted,
+                        now,
+                    );
+                } else if observed
+                    .duration_since(in_corridor_since)
+                    .map(|d| d >= self.recovery_cooldown)
+                    .unwrap_or(false)
+                {
+                    self.transition(
+                        SuspensionState::Active,
+                        SuspensionTransition::ResumedAfterCooldown,
+                        now,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Drop any recovery progress; the cooldown starts over with the next
+    /// in-corridor sample. Used when the companion's stream had a gap.
+    pub fn restart_recovery(&mut self, now: SystemTime) {
+        if let SuspensionState::Recovering { .. } = self.suspension {
+            self.suspend_consent(now);
+        }
+    }
+
+    /// Take the suspension audit records accumulated since the last drain.
+    pub fn drain_suspension_audit(&mut self) -> Vec<SuspensionAuditRecord> {
+        std::mem::take(&mut self.suspension_log)
+    }
+
+    fn transition(
+        &mut self,
+        to: SuspensionState,
+        transition: SuspensionTransition,
+        now: SystemTime,
+    ) {
+        let from = self.suspension;
+        self.suspension = to;
+        if self.consent_audit_log_enabled {
+            self.suspension_log.push(SuspensionAuditRecord {
+                wallet_did: self.wallet_did.clone(),
+                transition,
+                from,
+                to,
+                timestamp: now,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::{
+        AugFingerprintGuard, ConsentDecision, ConsentReason,
+    };
+    use crate::test_support::{at, pay, sample, shard};
+
+    /// Shard suspended at t=0 by a non-essential payment while out of corridor.
+    fn suspended_shard() -> AugFingerprintShard {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(0.9, 0));
+        let (_, reason, _) = AugFingerprintGuard::evaluate(&mut shard, &pay(1_000, false, 0), None);
+        assert_eq!(reason, ConsentReason::StateOutsideCorridor);
+        assert_eq!(shard.suspension, SuspensionState::Suspended { since: at(0) });
+        shard
+    }
+
+    fn transitions(shard: &mut AugFingerprintShard) -> Vec<SuspensionTransition> {
+        shard.drain_suspension_audit().iter().map(|r| r.transition).collect()
+    }
+
+    #[test]
+    fn cooldown_completes_only_on_fresh_in_corridor_samples() {
+        let mut shard = suspended_shard();
+        for t in (1..61).step_by(10) {
+            shard.update_neuro_state(sample(0.5, t));
+            assert!(shard.consent_suspended(), "t={}", t);
+        }
+        shard.update_neuro_state(sample(0.5, 61));
+        assert!(!shard.consent_suspended());
+        assert_eq!(
+            transitions(&mut shard),
+            [
+                SuspensionTransition::Suspended,
+                SuspensionTransition::RecoveryStarted,
+                SuspensionTransition::ResumedAfterCooldown,
+            ]
+        );
+    }
+
+    #[test]
+    fn silence_after_one_in_corridor_sample_does_not_lift_suspension() {
+        let mut shard = suspended_shard();
+        shard.update_neuro_state(sample(0.5, 1));
+
+        let request = pay(1_000, false, 600);
+        let (decision, reason, _) = AugFingerprintGuard::evaluate(&mut shard, &request, None);
+        assert_eq!(decision, ConsentDecision::Deny);
+        assert_eq!(reason, ConsentReason::ConsentSuspended);
+        assert!(matches!(shard.suspension, SuspensionState::Recovering { .. }));
+    }
+
+    #[test]
+    fn reporting_gap_restarts_the_cooldown() {
+        let mut shard = suspended_shard();
+        shard.update_neuro_state(sample(0.5, 1));
+        shard.update_neuro_state(sample(0.5, 21));
+        // 40s of silence is longer than neuro_state_max_age.
+        shard.update_neuro_state(sample(0.5, 61));
+        assert_eq!(
+            shard.suspension,
+            SuspensionState::Recovering {
+                suspended_since: at(0),
+                in_corridor_since: at(61),
+            }
+        );
+        shard.update_neuro_state(sample(0.5, 81));
+        shard.update_neuro_state(sample(0.5, 101));
+        assert!(shard.consent_suspended());
+        shard.update_neuro_state(sample(0.5, 121));
+        assert!(!shard.consent_suspended());
+    }
+
+    #[test]
+    fn leaving_the_corridor_interrupts_recovery() {
+        let mut shard = suspended_shard();
+        shard.update_neuro_state(sample(0.5, 1));
+        shard.update_neuro_state(sample(0.9, 10));
+        shard.update_neuro_state(sample(0.9, 13));
+        assert_eq!(shard.suspension, SuspensionState::Suspended { since: at(0) });
+        assert_eq!(
+            transitions(&mut shard).last(),
+            Some(&SuspensionTransition::RecoveryInterrupted)
+        );
+    }
+
+    #[test]
+    fn host_resume_is_honoured_outside_the_corridor() {
+        let mut shard = suspended_shard();
+        shard.host_resume(at(5));
+        assert_eq!(shard.suspension, SuspensionState::Active);
+        assert_eq!(
+            transitions(&mut shard).last(),
+            Some(&SuspensionTransition::ResumedByHost)
+        );
+    }
+}
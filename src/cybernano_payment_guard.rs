@@ -1,4 +1,8 @@
-use std::time::Instant;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{Clock, SystemClock};
 
 /// Abstracted lifeforce envelope for a cybernetic host.
 /// This is NOT energy metering; it encodes sustainable-integrity bands.
@@ -47,14 +51,15 @@ pub struct NanoDebitRequest {
 }
 
 /// Result of applying the guard.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NanoDebitDecision {
     pub approved: bool,
     pub reason: String,
     pub new_lifeforce_level: f32,
     pub new_blood_tokens: u64,
     pub hextrace: String,
-    pub decided_at: Instant,
+    /// Wall-clock time of the decision, from the guard's `Clock`.
+    pub decided_at: SystemTime,
 }
 
 /// Guard that enforces:
@@ -69,7 +74,17 @@ impl CyberNanoPaymentGuard {
         blood_ledger: &BloodTokenLedger,
         req: &NanoDebitRequest,
     ) -> NanoDebitDecision {
-        let now = Instant::now();
+        Self::eval_with_clock(lifeforce, blood_ledger, req, &SystemClock)
+    }
+
+    /// `eval`, stamping the decision with `clock` instead of the system time.
+    pub fn eval_with_clock(
+        lifeforce: &LifeforceEnvelope,
+        blood_ledger: &BloodTokenLedger,
+        req: &NanoDebitRequest,
+        clock: &dyn Clock,
+    ) -> NanoDebitDecision {
+        let now = clock.now();
 
         // 1. Hard block any attempt to use nanoswarm for direct merchant settlement.
         if req.class == NanoDebitClass::MerchantPayment {
@@ -0,0 +1,371 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use crate::consent_policy::PolicyThresholds;
+use crate::paycomp_augfingerprint_guard::{
+    AiConsentPolicy, AiConsentState, AugFingerprintGuard, AugFingerprintShard, ConsentDecision,
+    ConsentOutcome, ConsentReason, ControlMode, PaymentRequest,
+};
+
+/// Wallet settings a deferred payment was evaluated under.
+///
+/// A queued payment is only ever re-evaluated under the same settings; if any of
+/// them changed it is dropped and the host has to be prompted afresh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredContext {
+    pub control_mode: ControlMode,
+    pub ai_consent_policy: AiConsentPolicy,
+    pub policy_thresholds: PolicyThresholds,
+    pub max_auto_amount_mills: u64,
+    pub max_daily_spend_mills: u64,
+    pub caregiver_did: Option<String>,
+}
+
+impl AugFingerprintShard {
+    /// Snapshot of the settings a payment deferred now is evaluated under.
+    pub fn deferred_context(&self) -> DeferredContext {
+        DeferredContext {
+            control_mode: self.control_mode,
+            ai_consent_policy: self.ai_consent_policy,
+            policy_thresholds: self.policy_thresholds,
+            max_auto_amount_mills: self.max_auto_amount_mills,
+            max_daily_spend_mills: self.max_daily_spend_mills,
+            caregiver_did: self.active_caregiver().map(|c| c.caregiver_did.clone()),
+        }
+    }
+
+    fn enqueue_deferred(
+        &mut self,
+        request: &PaymentRequest,
+        reason: ConsentReason,
+        ttl: Duration,
+        retry_after: Duration,
+    ) -> u64 {
+        let context = self.deferred_context();
+        let now = request.now;
+        let queue = &mut self.deferred_queue;
+        let existing = queue.items.iter_mut().find(|p| {
+            p.request.merchant_id == request.merchant_id
+                && p.request.amount_mills == request.amount_mills
+        });
+        if let Some(item) = existing {
+            item.request = request.clone();
+            item.reason = reason;
+            item.expires_at = now + ttl;
+            item.retry_at = now + retry_after;
+            item.context = context;
+            return item.queue_id;
+        }
+
+        let queue_id = queue.next_queue_id;
+        queue.next_queue_id = queue.next_queue_id.wrapping_add(1);
+        queue.items.push(DeferredPayment {
+            queue_id,
+            request: request.clone(),
+            reason,
+            queued_at: now,
+            expires_at: now + ttl,
+            retry_at: now + retry_after,
+            context,
+        });
+        queue_id
+    }
+}
+
+/// Payment parked after a `Defer`, waiting for the host to stabilize.
+///
+/// Holds no consent: whatever the companion reported when the payment was
+/// deferred is never reused, the host is always asked again.
+#[derive(Debug, Clone)]
+pub struct DeferredPayment {
+    pub queue_id: u64,
+    pub request: PaymentRequest,
+    /// Reason of the latest `Defer`.
+    pub reason: ConsentReason,
+    pub queued_at: SystemTime,
+    /// Merchant TTL; the payment is dropped after this.
+    pub expires_at: SystemTime,
+    /// Not re-evaluated before this (the latest retry hint).
+    pub retry_at: SystemTime,
+    pub context: DeferredContext,
+}
+
+/// Per-wallet queue of deferred payments.
+///
+/// At most one entry per merchant and amount: deferring the same payment again
+/// refreshes the existing entry instead of queueing a duplicate.
+#[derive(Debug, Clone)]
+pub struct DeferredQueue {
+    pub items: Vec<DeferredPayment>,
+    pub next_queue_id: u64,
+}
+
+impl DeferredQueue {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            next_queue_id: 1,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, queue_id: u64) -> Option<&DeferredPayment> {
+        self.items.iter().find(|p| p.queue_id == queue_id)
+    }
+
+    /// Drop a queued payment, e.g. when the merchant cancels or the host declines.
+    pub fn cancel(&mut self, queue_id: u64) -> Option<DeferredPayment> {
+        let idx = self.items.iter().position(|p| p.queue_id == queue_id)?;
+        Some(self.items.remove(idx))
+    }
+}
+
+impl Default for DeferredQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What happened to a queued payment during `replay_deferred`.
+#[derive(Debug, Clone)]
+pub enum ReplayResult {
+    /// The host is fresh and stable and the retry time has come: prompt the host
+    /// again and pass the answer to `resume_deferred`. The payment stays queued.
+    ReadyToPrompt { queue_id: u64, request: PaymentRequest },
+    /// The merchant TTL ran out first.
+    Expired { queue_id: u64, request: PaymentRequest },
+    /// Wallet settings changed since the payment was queued; not re-evaluated.
+    ContextChanged { queue_id: u64, request: PaymentRequest },
+}
+
+/// Why a queued payment could not be resumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeError {
+    /// Not in the queue (never queued, cancelled or already settled).
+    UnknownPayment { queue_id: u64 },
+    /// The merchant TTL ran out; the payment has been dropped.
+    Expired { queue_id: u64 },
+    /// Wallet settings changed since it was queued; the payment has been dropped.
+    ContextChanged { queue_id: u64 },
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::UnknownPayment { queue_id } => {
+                write!(f, "no deferred payment {}", queue_id)
+            }
+            ResumeError::Expired { queue_id } => {
+                write!(f, "deferred payment {} expired", queue_id)
+            }
+            ResumeError::ContextChanged { queue_id } => {
+                write!(f, "wallet settings changed since payment {} was deferred", queue_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+impl AugFingerprintGuard {
+    /// `evaluate_outcome`, and park the payment in the shard's deferred queue if
+    /// the outcome is `Defer`, for `ttl` as set by the merchant.
+    ///
+    /// Returns the queue id alongside the outcome. A `Defer` waiting on the
+    /// caregiver is left to the co-approval flow and not queued.
+    pub fn evaluate_queued(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        ttl: Duration,
+    ) -> (ConsentOutcome, Option<u64>) {
+        let outcome = Self::evaluate_outcome(shard, request, ai_state);
+        let queue_id = match (outcome.decision, outcome.reason) {
+            (ConsentDecision::Defer, ConsentReason::CaregiverApprovalRequired) => None,
+            (ConsentDecision::Defer, reason) => {
+                let retry_after = outcome.retry_after.unwrap_or(Duration::ZERO);
+                Some(shard.enqueue_deferred(request, reason, ttl, retry_after))
+            }
+            _ => None,
+        };
+        (outcome, queue_id)
+    }
+
+    /// Drop expired payments and those whose wallet context changed, then, once
+    /// the host is fresh and stable again, report the ones whose retry time has
+    /// come as `ReadyToPrompt`.
+    ///
+    /// Call after `update_neuro_state`. Nothing is settled here: a deferred
+    /// payment only goes through once the host answers a new prompt, via
+    /// `resume_deferred`, so nothing is ever approved under settings or consent
+    /// the host didn't give for it.
+    pub fn replay_deferred(shard: &mut AugFingerprintShard, now: SystemTime) -> Vec<ReplayResult> {
+        let mut results = Vec::new();
+        let context = shard.deferred_context();
+        let policy = shard.consent_policy();
+        let stable = shard.neuro_state_fresh(now)
+            && !shard.stability.exit_pending()
+            && shard
+                .stability
+                .dwell(now)
+                .is_some_and(|d| d >= policy.min_stability_time());
+
+        let items = std::mem::take(&mut shard.deferred_queue.items);
+        let mut kept = Vec::with_capacity(items.len());
+        for item in items {
+            if now > item.expires_at {
+                results.push(ReplayResult::Expired {
+                    queue_id: item.queue_id,
+                    request: item.request,
+                });
+                continue;
+            }
+            if item.context != context {
+                results.push(ReplayResult::ContextChanged {
+                    queue_id: item.queue_id,
+                    request: item.request,
+                });
+                continue;
+            }
+            if stable && now >= item.retry_at {
+                results.push(ReplayResult::ReadyToPrompt {
+                    queue_id: item.queue_id,
+                    request: item.request.clone(),
+                });
+            }
+            kept.push(item);
+        }
+        shard.deferred_queue.items = kept;
+        results
+    }
+
+    /// Evaluate queued payment `queue_id` at `now` with the host's answer to the
+    /// new prompt, `ai_state`.
+    ///
+    /// Counts as a prompt like any evaluation. A payment that defers again stays
+    /// queued under the same id with a new retry time and its original TTL;
+    /// any other outcome takes it off the queue.
+    pub fn resume_deferred(
+        shard: &mut AugFingerprintShard,
+        queue_id: u64,
+        ai_state: Option<AiConsentState>,
+        now: SystemTime,
+    ) -> Result<ConsentOutcome, ResumeError> {
+        let mut item = shard
+            .deferred_queue
+            .cancel(queue_id)
+            .ok_or(ResumeError::UnknownPayment { queue_id })?;
+        if now > item.expires_at {
+            return Err(ResumeError::Expired { queue_id });
+        }
+        if item.context != shard.deferred_context() {
+            return Err(ResumeError::ContextChanged { queue_id });
+        }
+
+        let mut request = item.request.clone();
+        request.now = now;
+        let outcome = Self::evaluate_outcome(shard, &request, ai_state);
+        if outcome.decision == ConsentDecision::Defer
+            && outcome.reason != ConsentReason::CaregiverApprovalRequired
+        {
+            item.request = request;
+            item.reason = outcome.reason;
+            item.retry_at = now + outcome.retry_after.unwrap_or(Duration::ZERO);
+            shard.deferred_queue.items.push(item);
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at, pay, sample, shard};
+
+    const TTL: Duration = Duration::from_secs(600);
+
+    /// Essential payment deferred at t=0 with a confirmed companion state, host
+    /// outside the corridor; the host is steady again from t=100 to t=120.
+    fn deferred_then_steady() -> (AugFingerprintShard, u64) {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(0.9, 0));
+        let confirmed = Some(AiConsentState::Confirmed);
+        let (outcome, queue_id) =
+            AugFingerprintGuard::evaluate_queued(&mut shard, &pay(1_000, true, 0), confirmed, TTL);
+        assert_eq!(outcome.reason, ConsentReason::EssentialStateUnstable);
+        for t in [100, 110, 120] {
+            shard.update_neuro_state(sample(0.5, t));
+        }
+        (shard, queue_id.unwrap())
+    }
+
+    #[test]
+    fn replay_never_settles_on_the_stale_confirmation() {
+        let (mut shard, queue_id) = deferred_then_steady();
+        let results = AugFingerprintGuard::replay_deferred(&mut shard, at(120));
+        assert!(matches!(
+            results.as_slice(),
+            [ReplayResult::ReadyToPrompt { queue_id: id, .. }] if *id == queue_id
+        ));
+        assert_eq!(shard.deferred_queue.len(), 1);
+        assert_eq!(shard.payments_last_hour(at(120)), 0);
+
+        // The host's new answer decides, not the confirmation given at t=0.
+        let denied = Some(AiConsentState::Deny);
+        let outcome =
+            AugFingerprintGuard::resume_deferred(&mut shard, queue_id, denied, at(120)).unwrap();
+        assert_eq!(outcome.reason, ConsentReason::AiConsentNotConfirmed);
+        assert_eq!(outcome.decision, ConsentDecision::Defer);
+        assert_eq!(shard.payments_last_hour(at(120)), 0);
+    }
+
+    #[test]
+    fn resume_with_a_fresh_confirmation_settles_and_dequeues() {
+        let (mut shard, queue_id) = deferred_then_steady();
+        let confirmed = Some(AiConsentState::Confirmed);
+        let outcome =
+            AugFingerprintGuard::resume_deferred(&mut shard, queue_id, confirmed, at(120)).unwrap();
+        assert_eq!(outcome.decision, ConsentDecision::Allow);
+        assert!(shard.deferred_queue.is_empty());
+        let again = AugFingerprintGuard::resume_deferred(&mut shard, queue_id, confirmed, at(121));
+        assert_eq!(again.unwrap_err(), ResumeError::UnknownPayment { queue_id });
+    }
+
+    #[test]
+    fn nothing_is_ready_until_the_host_is_stable() {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(0.9, 0));
+        let (_, queue_id) =
+            AugFingerprintGuard::evaluate_queued(&mut shard, &pay(1_000, true, 0), None, TTL);
+        // Deferring the same payment again refreshes the entry.
+        let (_, again) =
+            AugFingerprintGuard::evaluate_queued(&mut shard, &pay(1_000, true, 1), None, TTL);
+        assert_eq!(again, queue_id);
+        assert_eq!(shard.deferred_queue.len(), 1);
+
+        assert!(AugFingerprintGuard::replay_deferred(&mut shard, at(10)).is_empty());
+        shard.update_neuro_state(sample(0.5, 100));
+        assert!(AugFingerprintGuard::replay_deferred(&mut shard, at(102)).is_empty());
+    }
+
+    #[test]
+    fn expired_and_changed_context_payments_are_dropped() {
+        let (mut shard, _) = deferred_then_steady();
+        let results = AugFingerprintGuard::replay_deferred(&mut shard, at(601));
+        assert!(matches!(results.as_slice(), [ReplayResult::Expired { .. }]));
+        assert!(shard.deferred_queue.is_empty());
+
+        let (mut shard, queue_id) = deferred_then_steady();
+        shard.max_auto_amount_mills = 10_000;
+        let resumed = AugFingerprintGuard::resume_deferred(&mut shard, queue_id, None, at(120));
+        assert_eq!(resumed.unwrap_err(), ResumeError::ContextChanged { queue_id });
+        assert!(shard.deferred_queue.is_empty());
+    }
+}
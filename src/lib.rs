@@ -0,0 +1,31 @@
+//! Paycomp consent guards for augmented-citizen wallets and POS terminals.
+
+pub mod audit_redaction;
+pub mod augfingerprint_aln;
+pub mod augfingerprint_corridor;
+pub mod caregiver_coapproval;
+pub mod clock;
+pub mod consent_audit_log;
+pub mod consent_episode;
+pub mod consent_fusion;
+pub mod consent_policy;
+pub mod consent_recovery;
+pub mod cybernano_payment_guard;
+pub mod deferred_queue;
+pub mod merchant_view;
+pub mod neuro_corridor;
+pub mod paycomp_au_guard;
+pub mod paycomp_augfingerprint_guard;
+pub mod rate_window;
+pub mod retry_hint;
+
+#[cfg(test)]
+pub(crate) mod test_support;
+
+// `paycomp/{guards,shards,spec_anchor,subcent,wallet}.rs` are the ledger-side
+// types. They import a `did_types` module that is not in this tree, so they stay
+// out of the build until it lands.
+pub mod paycomp {
+    pub mod aug_fingerprint_guard;
+    pub mod biophysical_network;
+}
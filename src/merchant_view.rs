@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::paycomp_augfingerprint_guard::{
+    ConsentAuditRecord, ConsentDecision, ConsentOutcome, ConsentReason,
+};
+
+/// Coarse reason bucket a merchant may see.
+///
+/// Deliberately lossy: corridor, stability, freshness and suspension all read as
+/// `HostNotReady`, so nothing about the host's inner state can be inferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCategory {
+    Approved,
+    HostNotReady,
+    ConsentNotConfirmed,
+    RateLimited,
+    SpendLimit,
+    RiskPolicy,
+}
+
+impl ReasonCategory {
+    pub fn from_reason(reason: ConsentReason) -> Self {
+        match reason {
+            ConsentReason::Ok => ReasonCategory::Approved,
+            ConsentReason::ConsentSuspended
+            | ConsentReason::StateOutsideCorridor
+            | ConsentReason::EssentialStateUnstable
+            | ConsentReason::StabilityTimeInsufficient
+            | ConsentReason::StabilityTimeInvalid
+            | ConsentReason::StabilityNotYetEstablished
+            | ConsentReason::NeuroStateStale
+            | ConsentReason::CorridorExitPending => ReasonCategory::HostNotReady,
+            ConsentReason::AiConsentNotConfirmed
+            | ConsentReason::CaregiverApprovalRequired
+            | ConsentReason::CaregiverUnavailable
+            | ConsentReason::CaregiverDeclined => ReasonCategory::ConsentNotConfirmed,
+            ConsentReason::PromptRateExceeded | ConsentReason::PaymentRateExceeded => {
+                ReasonCategory::RateLimited
+            }
+            ConsentReason::AmountOverLimit | ConsentReason::DailySpendExceeded => {
+                ReasonCategory::SpendLimit
+            }
+            ConsentReason::RiskScoresTooHigh => ReasonCategory::RiskPolicy,
+        }
+    }
+}
+
+/// What a merchant / POS integrator gets back from a consent evaluation.
+///
+/// Built from the guard output; holds no DID, amount, inner-state or channel data.
+/// `decision_id` matches the `ConsentAuditRecord` kept by the host, so a dispute
+/// can be resolved against the host's audit log without sharing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerchantDecisionView {
+    pub decision_id: Uuid,
+    pub decision: ConsentDecision,
+    pub category: ReasonCategory,
+    /// Suggested wait before re-prompting, for `Defer` only.
+    pub retry_after_ms: Option<u64>,
+}
+
+impl MerchantDecisionView {
+    /// View of one guard outcome. Without an audit record (audit disabled) a fresh
+    /// id is minted, so the view is still opaque and unique.
+    pub fn new(
+        decision: ConsentDecision,
+        reason: ConsentReason,
+        audit: Option<&ConsentAuditRecord>,
+    ) -> Self {
+        Self {
+            decision_id: audit.map_or_else(Uuid::new_v4, |a| a.decision_id),
+            decision,
+            category: ReasonCategory::from_reason(reason),
+            retry_after_ms: None,
+        }
+    }
+
+    /// View of a `ConsentOutcome` (`AugFingerprintGuard::evaluate_outcome` and
+    /// friends), retry hint included.
+    pub fn from_guard_output(output: &ConsentOutcome) -> Self {
+        let view = Self::new(output.decision, output.reason, output.audit.as_ref());
+        match output.retry_after {
+            Some(retry_after) => view.with_retry_after(retry_after),
+            None => view,
+        }
+    }
+
+    /// Attach a retry hint; ignored unless the decision is `Defer`.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        if self.decision == ConsentDecision::Defer {
+            self.retry_after_ms = Some(u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX));
+        }
+        self
+    }
+
+    /// Wire form for POS integrators.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::AugFingerprintGuard;
+    use crate::test_support::{pay, sample, shard, HOST_DID};
+
+    fn outcome(svalue: f32, is_essential_service: bool) -> ConsentOutcome {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(svalue, 0));
+        let request = pay(1_000, is_essential_service, 0);
+        AugFingerprintGuard::evaluate_outcome(&mut shard, &request, None)
+    }
+
+    #[test]
+    fn inner_state_reasons_all_read_as_host_not_ready() {
+        for reason in [
+            ConsentReason::ConsentSuspended,
+            ConsentReason::StateOutsideCorridor,
+            ConsentReason::EssentialStateUnstable,
+            ConsentReason::StabilityTimeInsufficient,
+            ConsentReason::NeuroStateStale,
+            ConsentReason::CorridorExitPending,
+        ] {
+            assert_eq!(ReasonCategory::from_reason(reason), ReasonCategory::HostNotReady);
+        }
+        assert_eq!(ReasonCategory::from_reason(ConsentReason::Ok), ReasonCategory::Approved);
+        assert_eq!(
+            ReasonCategory::from_reason(ConsentReason::CaregiverDeclined),
+            ReasonCategory::ConsentNotConfirmed
+        );
+        assert_eq!(
+            ReasonCategory::from_reason(ConsentReason::DailySpendExceeded),
+            ReasonCategory::SpendLimit
+        );
+    }
+
+    #[test]
+    fn view_shares_the_audit_decision_id_and_a_defer_retry_hint() {
+        let deferred = outcome(0.9, true);
+        assert_eq!(deferred.decision, ConsentDecision::Defer);
+        let view = MerchantDecisionView::from_guard_output(&deferred);
+        assert_eq!(view.decision_id, deferred.audit.as_ref().unwrap().decision_id);
+        assert_eq!(view.category, ReasonCategory::HostNotReady);
+        let retry_after = deferred.retry_after.unwrap();
+        assert_eq!(view.retry_after_ms, Some(retry_after.as_millis() as u64));
+
+        let denied = outcome(0.9, false);
+        let view = MerchantDecisionView::from_guard_output(&denied);
+        assert_eq!(view.retry_after_ms, None);
+        assert_eq!(view.with_retry_after(Duration::from_secs(5)).retry_after_ms, None);
+    }
+
+    #[test]
+    fn view_without_audit_gets_a_fresh_id_and_leaks_nothing() {
+        let reason = ConsentReason::StateOutsideCorridor;
+        let a = MerchantDecisionView::new(ConsentDecision::Deny, reason, None);
+        let b = MerchantDecisionView::new(ConsentDecision::Deny, reason, None);
+        assert_ne!(a.decision_id, b.decision_id);
+
+        let json = MerchantDecisionView::from_guard_output(&outcome(0.9, false)).to_json().unwrap();
+        assert!(json.contains("\"category\":\"host_not_ready\""), "{}", json);
+        for leak in [HOST_DID, "corridor", "m-1", "amount"] {
+            assert!(!json.contains(leak), "{} in {}", leak, json);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::time::{Duration, SystemTime};
+
+use crate::paycomp_augfingerprint_guard::{AugFingerprintShard, NeuroState};
+
+/// One S_t / L_t band: `smin <= S_t <= smax` and `L_t <= loadmax`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorridorBand {
+    pub smin: f32,
+    pub smax: f32,
+    pub loadmax: f32,
+}
+
+impl CorridorBand {
+    pub fn contains(&self, svalue: f32, loadvalue: f32) -> bool {
+        svalue >= self.smin && svalue <= self.smax && loadvalue <= self.loadmax
+    }
+}
+
+/// Hysteresis corridor for S_t / L_t.
+///
+/// The state must come inside the narrower `entry` band to count as in corridor,
+/// and only counts as out once it leaves the wider `exit` band. Values between the
+/// two keep whatever status they had, so jitter on a bound can't flip decisions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeuroCorridor {
+    pub entry: CorridorBand,
+    pub exit: CorridorBand,
+}
+
+impl NeuroCorridor {
+    /// Exit band = entry band widened by `s_margin` on both S_t bounds and by
+    /// `load_margin` on the load ceiling, clamped to [0, 1].
+    pub fn with_hysteresis(entry: CorridorBand, s_margin: f32, load_margin: f32) -> Self {
+        let exit = CorridorBand {
+            smin: (entry.smin - s_margin).max(0.0),
+            smax: (entry.smax + s_margin).min(1.0),
+            loadmax: (entry.loadmax + load_margin).min(1.0),
+        };
+        Self { entry, exit }
+    }
+}
+
+/// Debounced corridor status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorridorStatus {
+    /// No neuro state has been observed yet.
+    Unknown,
+    Inside,
+    Outside,
+}
+
+/// Debounced corridor tracker, fed with every neuro-state update.
+///
+/// Shared by the wallet guard (POS / XR / agent payments) and the POS prompt guard
+/// so both read the same in-corridor status. Leaving the exit band only counts once
+/// the state has stayed out for `exit_debounce`; a short dip keeps the current stay
+/// (and its dwell time), but guards defer while `exit_pending` is set.
+///
+/// Corridor entry and exit times come from the companion's stream, so the dwell
+/// time is known before any payment is attempted.
+#[derive(Debug, Clone)]
+pub struct StabilityTracker {
+    pub exit_debounce: Duration,
+    status: CorridorStatus,
+    /// First observation outside the exit band while still `Inside`.
+    exit_pending_since: Option<SystemTime>,
+    /// Start of the current stay inside the corridor.
+    entered_at: Option<SystemTime>,
+    /// Time of the last debounced exit (the first out-of-band sample).
+    exited_at: Option<SystemTime>,
+    last_observed: Option<SystemTime>,
+    observations: u64,
+}
+
+/// Snapshot of the stability tracker for diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StabilityReport {
+    pub status: CorridorStatus,
+    pub entered_at: Option<SystemTime>,
+    pub exited_at: Option<SystemTime>,
+    /// Start of an exit that is still being debounced.
+    pub exit_pending_since: Option<SystemTime>,
+    pub last_observed: Option<SystemTime>,
+    pub observations: u64,
+    /// Time spent inside the corridor so far, as of the report time.
+    pub dwell: Option<Duration>,
+    pub min_stability_time: Duration,
+    /// `dwell >= min_stability_time`.
+    pub stable: bool,
+    pub corridor: NeuroCorridor,
+}
+
+/// Default time S_t / L_t must stay outside the exit band before it counts as an exit.
+pub const DEFAULT_EXIT_DEBOUNCE: Duration = Duration::from_secs(2);
+
+impl StabilityTracker {
+    pub fn new(exit_debounce: Duration) -> Self {
+        Self {
+            exit_debounce,
+            status: CorridorStatus::Unknown,
+            exit_pending_since: None,
+            entered_at: None,
+            exited_at: None,
+            last_observed: None,
+            observations: 0,
+        }
+    }
+
+    pub fn status(&self) -> CorridorStatus {
+        self.status
+    }
+
+    pub fn is_inside(&self) -> bool {
+        self.status == CorridorStatus::Inside
+    }
+
+    /// Whether the latest sample was outside the exit band while the exit is still
+    /// being debounced. The status stays `Inside` so the stay isn't reset, but such
+    /// a sample must never authorize anything.
+    pub fn exit_pending(&self) -> bool {
+        self.exit_pending_since.is_some()
+    }
+
+    /// Start of the current stay inside the corridor, `None` while outside.
+    pub fn entered_at(&self) -> Option<SystemTime> {
+        self.entered_at
+    }
+
+    pub fn exited_at(&self) -> Option<SystemTime> {
+        self.exited_at
+    }
+
+    pub fn last_observed(&self) -> Option<SystemTime> {
+        self.last_observed
+    }
+
+    /// Forget the current stay; the next observation decides the status afresh
+    /// against the entry band. Exit history and counters are kept.
+    pub fn restart(&mut self) {
+        self.status = CorridorStatus::Unknown;
+        self.exit_pending_since = None;
+        self.entered_at = None;
+    }
+
+    /// How long the state has been inside the corridor as of `now`.
+    /// `None` while outside; zero if `now` is before the entry (clock skew).
+    pub fn dwell(&self, now: SystemTime) -> Option<Duration> {
+        self.entered_at.map(|t0| now.duration_since(t0).unwrap_or(Duration::ZERO))
+    }
+
+    /// Feed one neuro-state snapshot; returns the debounced status afterwards.
+    pub fn observe(&mut self, corridor: &NeuroCorridor, state: &NeuroState) -> CorridorStatus {
+        let at = state.last_update;
+        self.last_observed = Some(at);
+        self.observations = self.observations.saturating_add(1);
+        match self.status {
+            CorridorStatus::Inside => {
+                if corridor.exit.contains(state.svalue, state.loadvalue) {
+                    self.exit_pending_since = None;
+                } else {
+                    let since = *self.exit_pending_since.get_or_insert(at);
+                    let out_for = at.duration_since(since).unwrap_or(Duration::ZERO);
+                    if out_for >= self.exit_debounce {
+                        self.status = CorridorStatus::Outside;
+                        self.exit_pending_since = None;
+                        self.entered_at = None;
+                        self.exited_at = Some(since);
+                    }
+                }
+            }
+            CorridorStatus::Unknown | CorridorStatus::Outside => {
+                if corridor.entry.contains(state.svalue, state.loadvalue) {
+                    self.status = CorridorStatus::Inside;
+                    self.entered_at = Some(at);
+                } else {
+                    self.status = CorridorStatus::Outside;
+                }
+            }
+        }
+        self.status
+    }
+}
+
+impl Default for StabilityTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_EXIT_DEBOUNCE)
+    }
+}
+
+impl AugFingerprintShard {
+    /// Diagnostics view of the corridor / stability state as of `now`.
+    pub fn stability_report(&self, now: SystemTime) -> StabilityReport {
+        let t = &self.stability;
+        let dwell = t.dwell(now);
+        let min_stability_time = self.consent_policy().min_stability_time();
+        StabilityReport {
+            status: t.status,
+            entered_at: t.entered_at,
+            exited_at: t.exited_at,
+            exit_pending_since: t.exit_pending_since,
+            last_observed: t.last_observed,
+            observations: t.observations,
+            dwell,
+            min_stability_time,
+            stable: dwell.is_some_and(|d| d >= min_stability_time),
+            corridor: self.corridor(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at, neuro_corridor as corridor, sample, shard};
+
+    #[test]
+    fn entry_needs_the_narrow_band_and_exit_the_wide_one() {
+        let c = corridor();
+        let mut t = StabilityTracker::default();
+        assert_eq!(t.observe(&c, &sample(0.62, 0)), CorridorStatus::Outside);
+        assert_eq!(t.observe(&c, &sample(0.58, 1)), CorridorStatus::Inside);
+        // Between the bands: stays inside, no exit pending.
+        assert_eq!(t.observe(&c, &sample(0.64, 2)), CorridorStatus::Inside);
+        assert!(!t.exit_pending());
+        assert_eq!(t.entered_at(), Some(at(1)));
+    }
+
+    #[test]
+    fn short_dip_keeps_the_stay_but_is_flagged_pending() {
+        let c = corridor();
+        let mut t = StabilityTracker::default();
+        t.observe(&c, &sample(0.5, 0));
+        assert_eq!(t.observe(&c, &sample(0.98, 10)), CorridorStatus::Inside);
+        assert!(t.exit_pending());
+        assert_eq!(t.observe(&c, &sample(0.5, 11)), CorridorStatus::Inside);
+        assert!(!t.exit_pending());
+        assert_eq!(t.dwell(at(11)), Some(Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn exit_counts_from_the_first_sample_out_once_debounced() {
+        let c = corridor();
+        let mut t = StabilityTracker::default();
+        t.observe(&c, &sample(0.5, 0));
+        t.observe(&c, &sample(0.9, 10));
+        t.observe(&c, &sample(0.9, 11));
+        assert!(t.is_inside());
+        assert_eq!(t.observe(&c, &sample(0.9, 12)), CorridorStatus::Outside);
+        assert_eq!(t.exited_at(), Some(at(10)));
+        assert_eq!((t.entered_at(), t.dwell(at(12))), (None, None));
+        assert!(!t.exit_pending());
+    }
+
+    #[test]
+    fn restart_forgets_the_stay() {
+        let c = corridor();
+        let mut t = StabilityTracker::default();
+        t.observe(&c, &sample(0.5, 0));
+        t.restart();
+        assert_eq!(t.status(), CorridorStatus::Unknown);
+        assert_eq!(t.observe(&c, &sample(0.5, 60)), CorridorStatus::Inside);
+        assert_eq!(t.entered_at(), Some(at(60)));
+    }
+
+    #[test]
+    fn stability_report_tracks_dwell_from_the_companion_stream() {
+        let mut shard = shard();
+        let report = shard.stability_report(at(0));
+        assert_eq!(report.status, CorridorStatus::Unknown);
+        assert_eq!((report.dwell, report.stable), (None, false));
+
+        shard.update_neuro_state(sample(0.5, 10));
+        shard.update_neuro_state(sample(0.5, 13));
+        let report = shard.stability_report(at(13));
+        assert_eq!(report.entered_at, Some(at(10)));
+        assert_eq!(report.dwell, Some(Duration::from_secs(3)));
+        assert_eq!(report.min_stability_time, Duration::from_secs(5));
+        assert!(!report.stable);
+        assert_eq!(report.observations, 2);
+        assert!(shard.stability_report(at(15)).stable);
+
+        shard.update_neuro_state(sample(0.9, 16));
+        let report = shard.stability_report(at(16));
+        assert_eq!(report.exit_pending_since, Some(at(16)));
+        assert_eq!(report.last_observed, Some(at(16)));
+    }
+
+    #[test]
+    fn shard_corridor_uses_the_tighter_load_ceiling() {
+        let mut shard = shard();
+        shard.update_neuro_state(sample(0.5, 0));
+        let c = shard.corridor();
+        assert_eq!(c.entry.loadmax, 0.4);
+        assert!((c.exit.loadmax - 0.45).abs() < 1e-6);
+        assert!((c.exit.smin - 0.35).abs() < 1e-6);
+    }
+}
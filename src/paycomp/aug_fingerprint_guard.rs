@@ -1,4 +1,28 @@
-use std::time::{Duration, SystemTime};
+//! Legacy AugFingerprint guard API.
+//!
+//! `crate::paycomp_augfingerprint_guard` owns the single guard implementation.
+//! This module keeps the original surface for existing callers: the two-argument
+//! `evaluate_payment` (deprecated; it drops the reason) and
+//! `evaluate_payment_with_reason`, plus `NeuroState`, `KerScore` and
+//! `ConsentAuditRecord` with their original field names. Each converts from its
+//! unified counterpart.
+//!
+//! The legacy `ConsentAuditRecord` here has `s_value` / `load_value` as
+//! `Option<f32>`; the unified `paycomp_augfingerprint_guard::ConsentAuditRecord`
+//! carries `inner_state: InnerStateRecord` instead. `AugFingerprintShard` is the
+//! unified shard, so its `neuro_state` / `ker_score` fields hold the unified
+//! types (`svalue`, `ecoimpact`, ...); convert them with `NeuroState::from` /
+//! `KerScore::from`.
+
+use std::time::SystemTime;
+
+pub use crate::paycomp_augfingerprint_guard::{
+    AiConsentPolicy, AugFingerprintShard, ConsentDecision, ConsentReason, ControlMode,
+    PaymentRequest,
+};
+
+use crate::audit_redaction::InnerStateRecord;
+use crate::paycomp_augfingerprint_guard as unified;
 
 /// Core scalar for K/E/R scoring.
 #[derive(Debug, Clone, Copy)]
@@ -8,308 +32,204 @@ pub struct KerScore {
     pub risk_of_harm: f32, // 0.0–1.0
 }
 
-/// Internal neuro/actuator state snapshot.
-#[derive(Debug, Clone, Copy)]
-pub struct NeuroState {
-    pub s_value: f32,          // normalized internal state S_t
-    pub load_value: f32,       // organic_cpu load L_t
-    pub s_min: f32,            // lower bound of safe corridor
-    pub s_max: f32,            // upper bound of safe corridor
-    pub load_max: f32,         // maximum allowed load in safe band
-    pub last_update: SystemTime,
-}
-
-/// Policy for AI‑consent behavior.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AiConsentPolicy {
-    Conservative,
-    Balanced,
-}
-
-/// Control mode for user interface.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ControlMode {
-    InternalBiophysical,
-    ExternalSwitch,
-    Voice,
-    Mixed,
+impl From<KerScore> for unified::KerScore {
+    fn from(k: KerScore) -> Self {
+        Self {
+            knowledge: k.knowledge,
+            ecoimpact: k.eco_impact,
+            riskofharm: k.risk_of_harm,
+        }
+    }
 }
 
-/// Result of a consent evaluation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ConsentDecision {
-    Allow,
-    Deny,
-    Defer,
+impl From<unified::KerScore> for KerScore {
+    fn from(k: unified::KerScore) -> Self {
+        Self {
+            knowledge: k.knowledge,
+            eco_impact: k.ecoimpact,
+            risk_of_harm: k.riskofharm,
+        }
+    }
 }
 
-/// Shard representing a single Aug_Fingerprint wallet for an implanted NFC user.
-#[derive(Debug, Clone)]
-pub struct AugFingerprintShard {
-    pub wallet_did: String,
-    pub interface_type: String,     // "implanted_nfc"
-    pub control_mode: ControlMode,  // InternalBiophysical for your profile
-    pub assist_mode: bool,          // always true for this profile
-    pub direct_user_gesture: bool,  // false for internal‑only control
-    pub consent_channel: String,    // "AI_inferred_state_only"
-    pub quantified_learning: bool,  // true when AI is learning lane patterns
-
-    // Accessibility and neuro/latency profile
-    pub speech_reliable: bool,
-    pub mobility_profile: String,
-    pub oculus_cortex_calibrated: bool,
-    pub latency_profile: String,    // e.g., "spiky"
-    pub max_cognitive_load: f32,    // normalized 0.0–1.0
-
-    // Spending corridors
-    pub max_auto_amount_mills: u64, // sub‑cent unit: 1 = 0.001 USD
-    pub max_daily_spend_mills: u64,
-    pub max_payments_per_hour: u32,
-    pub max_prompts_per_hour: u32,
-
-    // Quantified‑learning consent parameters
-    pub learning_mode: String,          // "quantified_lane_switching"
-    pub ai_consent_policy: AiConsentPolicy,
-    pub min_stability_time: Duration,   // minimum time in safe corridor
-    pub consent_suspended: bool,
-
-    // Dynamic state
-    pub neuro_state: NeuroState,
-    pub stable_since: Option<SystemTime>,
-    pub payments_last_hour: u32,
-    pub prompts_last_hour: u32,
-    pub last_reset_window: SystemTime,
-
-    // Risk/eco scoring
-    pub ker_score: KerScore,
-    pub r_privacy: f32,
-    pub r_fraud: f32,
-    pub r_tracking: f32,
-    pub e_accessibility: f32,
-
-    // Audit
-    pub consent_audit_log_enabled: bool,
+/// Internal neuro/actuator state snapshot.
+///
+/// `AugFingerprintShard::update_neuro_state` accepts it directly.
+#[derive(Debug, Clone, Copy)]
+pub struct NeuroState {
+    pub s_value: f32,    // normalized internal state S_t
+    pub load_value: f32, // organic_cpu load L_t
+    pub s_min: f32,      // lower bound of safe corridor
+    pub s_max: f32,      // upper bound of safe corridor
+    pub load_max: f32,   // maximum allowed load in safe band
+    pub last_update: SystemTime,
 }
 
-impl AugFingerprintShard {
-    pub fn new(wallet_did: String, now: SystemTime) -> Self {
+impl From<NeuroState> for unified::NeuroState {
+    fn from(ns: NeuroState) -> Self {
         Self {
-            wallet_did,
-            interface_type: "implanted_nfc".to_string(),
-            control_mode: ControlMode::InternalBiophysical,
-            assist_mode: true,
-            direct_user_gesture: false,
-            consent_channel: "AI_inferred_state_only".to_string(),
-            quantified_learning: true,
-            speech_reliable: false,
-            mobility_profile: "limited_precision_low_frequency".to_string(),
-            oculus_cortex_calibrated: false,
-            latency_profile: "spiky".to_string(),
-            max_cognitive_load: 0.4,
-
-            max_auto_amount_mills: 50_000,      // $50.000 default cap
-            max_daily_spend_mills: 200_000,     // $200.000 per day
-            max_payments_per_hour: 6,
-            max_prompts_per_hour: 10,
-
-            learning_mode: "quantified_lane_switching".to_string(),
-            ai_consent_policy: AiConsentPolicy::Conservative,
-            min_stability_time: Duration::from_secs(5),
-            consent_suspended: false,
-
-            neuro_state: NeuroState {
-                s_value: 0.0,
-                load_value: 0.0,
-                s_min: 0.4,
-                s_max: 0.6,
-                load_max: 0.5,
-                last_update: now,
-            },
-            stable_since: None,
-            payments_last_hour: 0,
-            prompts_last_hour: 0,
-            last_reset_window: now,
-
-            ker_score: KerScore {
-                knowledge: 0.9,
-                eco_impact: 0.9,
-                risk_of_harm: 0.15,
-            },
-            r_privacy: 0.2,
-            r_fraud: 0.2,
-            r_tracking: 0.2,
-            e_accessibility: 0.9,
-
-            consent_audit_log_enabled: true,
+            svalue: ns.s_value,
+            loadvalue: ns.load_value,
+            smin: ns.s_min,
+            smax: ns.s_max,
+            loadmax: ns.load_max,
+            last_update: ns.last_update,
         }
     }
+}
 
-    pub fn update_neuro_state(&mut self, state: NeuroState) {
-        self.neuro_state = state;
-    }
-
-    pub fn reset_counters_if_needed(&mut self, now: SystemTime) {
-        if let Ok(delta) = now.duration_since(self.last_reset_window) {
-            if delta >= Duration::from_secs(3600) {
-                self.payments_last_hour = 0;
-                self.prompts_last_hour = 0;
-                self.last_reset_window = now;
-            }
+impl From<unified::NeuroState> for NeuroState {
+    fn from(ns: unified::NeuroState) -> Self {
+        Self {
+            s_value: ns.svalue,
+            load_value: ns.loadvalue,
+            s_min: ns.smin,
+            s_max: ns.smax,
+            load_max: ns.loadmax,
+            last_update: ns.last_update,
         }
     }
 }
 
-/// Payment request as seen by the guard.
-#[derive(Debug, Clone)]
-pub struct PaymentRequest {
-    pub merchant_id: String,
-    pub region_id: String,
-    pub amount_mills: u64,
-    pub is_essential_service: bool,
-    pub now: SystemTime,
-}
-
 /// Audit record for consent decisions.
+///
+/// `s_value` / `load_value` are only `Some` when the shard's `audit_redaction`
+/// is `Raw`, so redacted values never leak through here.
 #[derive(Debug, Clone)]
 pub struct ConsentAuditRecord {
     pub wallet_did: String,
     pub merchant_id: String,
     pub amount_mills: u64,
     pub decision: ConsentDecision,
+    pub reason: ConsentReason,
     pub timestamp: SystemTime,
-    pub s_value: f32,
-    pub load_value: f32,
+    pub s_value: Option<f32>,
+    pub load_value: Option<f32>,
+}
+
+impl From<&unified::ConsentAuditRecord> for ConsentAuditRecord {
+    fn from(r: &unified::ConsentAuditRecord) -> Self {
+        let (s_value, load_value) = match r.inner_state {
+            InnerStateRecord::Raw {
+                s_value,
+                load_value,
+                ..
+            } => (Some(s_value), Some(load_value)),
+            InnerStateRecord::Redacted(_) => (None, None),
+        };
+        Self {
+            wallet_did: r.wallet_did.clone(),
+            merchant_id: r.merchant_id.clone(),
+            amount_mills: r.amount_mills,
+            decision: r.decision,
+            reason: r.reason,
+            timestamp: r.timestamp,
+            s_value,
+            load_value,
+        }
+    }
 }
 
 /// Core guard that enforces the internal‑state corridor consent model.
 pub struct AugFingerprintGuard;
 
 impl AugFingerprintGuard {
+    /// Evaluate without an AI-consent macro-state, with the original signature.
+    ///
+    /// The reason is dropped, and with `consent_audit_log_enabled = false` there
+    /// is no audit record to read it from either. Use `evaluate_payment_with_reason`,
+    /// or the unified guard's `evaluate_outcome` for the retry hint on `Defer`.
+    #[deprecated(note = "drops the reason; use evaluate_payment_with_reason")]
     pub fn evaluate_payment(
         shard: &mut AugFingerprintShard,
         request: &PaymentRequest,
     ) -> (ConsentDecision, Option<ConsentAuditRecord>) {
-        shard.reset_counters_if_needed(request.now);
-
-        if shard.consent_suspended && !request.is_essential_service {
-            return Self::deny_with_audit(shard, request, "consent_suspended");
-        }
-
-        if shard.prompts_last_hour >= shard.max_prompts_per_hour && !request.is_essential_service {
-            return Self::deny_with_audit(shard, request, "prompt_rate_exceeded");
-        }
-
-        if shard.payments_last_hour >= shard.max_payments_per_hour && !request.is_essential_service {
-            return Self::deny_with_audit(shard, request, "payment_rate_exceeded");
-        }
-
-        if request.amount_mills > shard.max_auto_amount_mills {
-            if !request.is_essential_service {
-                return Self::deny_with_audit(shard, request, "amount_over_limit");
-            }
-        }
-
-        let ns = shard.neuro_state;
-        let within_s_corridor = ns.s_value >= ns.s_min && ns.s_value <= ns.s_max;
-        let within_load_band = ns.load_value <= ns.load_max && ns.load_value <= shard.max_cognitive_load;
-
-        if !within_s_corridor || !within_load_band {
-            if !request.is_essential_service {
-                shard.consent_suspended = true;
-                return Self::deny_with_audit(shard, request, "state_outside_corridor");
-            } else {
-                return Self::defer_with_audit(shard, request, "essential_state_unstable");
-            }
-        }
-
-        let now = request.now;
-        let stable_since = match shard.stable_since {
-            Some(t) => {
-                if let Ok(delta) = now.duration_since(t) {
-                    if delta >= shard.min_stability_time {
-                        t
-                    } else {
-                        shard.stable_since = Some(now);
-                        return Self::defer_with_audit(shard, request, "stability_time_insufficient");
-                    }
-                } else {
-                    shard.stable_since = Some(now);
-                    return Self::defer_with_audit(shard, request, "stability_time_invalid");
-                }
-            }
-            None => {
-                shard.stable_since = Some(now);
-                return Self::defer_with_audit(shard, request, "stability_not_yet_established");
-            }
-        };
+        let (decision, _reason, audit) = Self::evaluate_payment_with_reason(shard, request);
+        (decision, audit)
+    }
 
-        let _ = stable_since; // kept for clarity, can be used for logging
+    /// `evaluate_payment` plus the reason; `ConsentReason::as_str` gives the
+    /// original snake_case codes.
+    pub fn evaluate_payment_with_reason(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+    ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
+        let (decision, reason, audit) =
+            unified::AugFingerprintGuard::evaluate(shard, request, None);
+        (decision, reason, audit.as_ref().map(ConsentAuditRecord::from))
+    }
+}
 
-        if shard.ai_consent_policy == AiConsentPolicy::Conservative {
-            if shard.r_fraud > 0.5 || shard.r_privacy > 0.5 || shard.r_tracking > 0.5 {
-                return Self::deny_with_audit(shard, request, "risk_scores_too_high");
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{pay, sample, shard};
 
-        shard.payments_last_hour += 1;
-        shard.prompts_last_hour += 1;
+    /// Legacy-shaped companion report in corridor at `t`.
+    fn in_corridor(t: u64) -> NeuroState {
+        NeuroState::from(sample(0.5, t))
+    }
 
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Allow,
-                timestamp: request.now,
-                s_value: shard.neuro_state.s_value,
-                load_value: shard.neuro_state.load_value,
-            })
-        } else {
-            None
-        };
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_entry_point_keeps_its_signature_and_field_names() {
+        let mut shard = shard();
+        shard.update_neuro_state(in_corridor(0));
+        shard.update_neuro_state(in_corridor(20));
+
+        let (decision, audit) =
+            AugFingerprintGuard::evaluate_payment(&mut shard, &pay(1_000, false, 20));
+        assert_eq!(decision, ConsentDecision::Allow);
+        let audit = audit.unwrap();
+        assert_eq!(audit.merchant_id, "m-1");
+        assert_eq!(audit.reason, ConsentReason::Ok);
+        // Bands redaction is the default: no raw values in the legacy record either.
+        assert_eq!((audit.s_value, audit.load_value), (None, None));
+
+        let back = NeuroState::from(shard.neuro_state);
+        assert_eq!((back.s_value, back.load_max), (0.5, 0.5));
+    }
 
-        (ConsentDecision::Allow, audit)
+    #[test]
+    fn legacy_record_carries_the_reason_and_raw_values_only_when_unredacted() {
+        let mut shard = shard();
+        shard.audit_redaction = crate::audit_redaction::AuditRedaction::Raw;
+        shard.update_neuro_state(in_corridor(0));
+        shard.update_neuro_state(in_corridor(20));
+
+        let mut big = pay(1_000, false, 20);
+        big.amount_mills = shard.max_auto_amount_mills + 1;
+        let (decision, reason, audit) =
+            AugFingerprintGuard::evaluate_payment_with_reason(&mut shard, &big);
+        assert_eq!((decision, reason), (ConsentDecision::Deny, ConsentReason::AmountOverLimit));
+        let audit = audit.unwrap();
+        assert_eq!(audit.reason, ConsentReason::AmountOverLimit);
+        assert_eq!((audit.s_value, audit.load_value), (Some(0.5), Some(0.2)));
     }
 
-    fn deny_with_audit(
-        shard: &AugFingerprintShard,
-        request: &PaymentRequest,
-        _reason: &str,
-    ) -> (ConsentDecision, Option<ConsentAuditRecord>) {
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Deny,
-                timestamp: request.now,
-                s_value: shard.neuro_state.s_value,
-                load_value: shard.neuro_state.load_value,
-            })
-        } else {
-            None
-        };
-        (ConsentDecision::Deny, audit)
+    #[test]
+    fn reason_is_returned_even_without_an_audit_log() {
+        let mut shard = shard();
+        shard.consent_audit_log_enabled = false;
+        shard.update_neuro_state(in_corridor(0));
+        shard.update_neuro_state(in_corridor(20));
+
+        let mut big = pay(1_000, false, 20);
+        big.amount_mills = shard.max_auto_amount_mills + 1;
+        let (decision, reason, audit) =
+            AugFingerprintGuard::evaluate_payment_with_reason(&mut shard, &big);
+        assert_eq!((decision, reason), (ConsentDecision::Deny, ConsentReason::AmountOverLimit));
+        assert!(audit.is_none());
     }
 
-    fn defer_with_audit(
-        shard: &AugFingerprintShard,
-        request: &PaymentRequest,
-        _reason: &str,
-    ) -> (ConsentDecision, Option<ConsentAuditRecord>) {
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Defer,
-                timestamp: request.now,
-                s_value: shard.neuro_state.s_value,
-                load_value: shard.neuro_state.load_value,
-            })
-        } else {
-            None
-        };
-        (ConsentDecision::Defer, audit)
+    #[test]
+    fn evaluation_before_corridor_entry_is_stability_time_invalid() {
+        let mut shard = shard();
+        shard.update_neuro_state(in_corridor(10));
+
+        let (decision, reason, _) =
+            AugFingerprintGuard::evaluate_payment_with_reason(&mut shard, &pay(1_000, false, 5));
+        assert_eq!(decision, ConsentDecision::Defer);
+        assert_eq!(reason.as_str(), "stability_time_invalid");
     }
 }
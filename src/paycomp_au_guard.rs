@@ -1,56 +1,579 @@
-use std::time::Instant;
-use crate::augfingerprint_corridor::AugFingerprintCorridor;
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+use crate::augfingerprint_corridor::{AugFingerprintCorridor, PromptStakes};
+use crate::clock::{Clock, SystemClock};
+use crate::neuro_corridor::StabilityTracker;
+use crate::rate_window::SlidingWindow;
+use crate::retry_hint::retry_hint;
 
+/// Latency samples kept per wallet; older ones age out.
+pub const LATENCY_WINDOW: usize = 50;
+
+/// Fewest samples before the measured distribution overrides the expected latency.
+pub const MIN_LATENCY_SAMPLES: usize = 5;
+
+/// Measured consent latencies (prompt shown to answer given), in ms.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub samples: VecDeque<u64>,
+}
+
+impl LatencyStats {
+    pub fn record(&mut self, latency_ms: u64) {
+        self.samples.push_back(latency_ms);
+        while self.samples.len() > LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile `pct` (0–100), once there are enough samples.
+    pub fn percentile_ms(&self, pct: u32) -> Option<u64> {
+        if self.samples.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * pct.min(100) as usize).div_ceil(100).max(1);
+        Some(sorted[rank - 1])
+    }
+
+    pub fn median_ms(&self) -> Option<u64> {
+        self.percentile_ms(50)
+    }
+
+    pub fn p90_ms(&self) -> Option<u64> {
+        self.percentile_ms(90)
+    }
+}
+
+/// Prompt budgets and measured latency of one wallet.
+///
+/// The state is not keyed by wallet: keep one `PromptState` (and so one
+/// `AugCitizenPosGuard`) per wallet DID, as its `AugFingerprintCorridor` is.
+/// Sharing one across hosts would pace each host by the others' answers.
 #[derive(Debug, Clone)]
 pub struct PromptState {
-    pub prompts_issued_this_hour: u32,
-    pub hour_window_started_at: Instant,
+    /// Prompts presented in the sliding one-hour window, any stakes.
+    pub prompts: SlidingWindow,
+    /// Per-stakes-class prompts in the same window.
+    pub informational: SlidingWindow,
+    pub low_value_payments: SlidingWindow,
+    pub high_stakes: SlidingWindow,
+    /// How long this host actually takes to answer, from `end_prompt`.
+    pub latency: LatencyStats,
+    pub next_prompt_id: u64,
+}
+
+impl PromptState {
+    pub fn new() -> Self {
+        Self {
+            prompts: SlidingWindow::hourly(),
+            informational: SlidingWindow::hourly(),
+            low_value_payments: SlidingWindow::hourly(),
+            high_stakes: SlidingWindow::hourly(),
+            latency: LatencyStats::default(),
+            next_prompt_id: 1,
+        }
+    }
+
+    /// Prompts of any stakes presented in the hour before `now`.
+    pub fn prompts_last_hour(&self, now: SystemTime) -> u32 {
+        self.prompts.count(now)
+    }
+
+    pub fn stakes_window(&self, stakes: PromptStakes) -> &SlidingWindow {
+        match stakes {
+            PromptStakes::Informational => &self.informational,
+            PromptStakes::LowValuePayment => &self.low_value_payments,
+            PromptStakes::HighStakes => &self.high_stakes,
+        }
+    }
+
+    fn stakes_window_mut(&mut self, stakes: PromptStakes) -> &mut SlidingWindow {
+        match stakes {
+            PromptStakes::Informational => &mut self.informational,
+            PromptStakes::LowValuePayment => &mut self.low_value_payments,
+            PromptStakes::HighStakes => &mut self.high_stakes,
+        }
+    }
+}
+
+impl Default for PromptState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hourly budget that blocked a prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptBudget {
+    /// `max_prompts_per_hour`, shared by every stakes class.
+    AllPrompts,
+    /// The class's own budget (`max_decisions_per_hour` for `HighStakes`).
+    Stakes(PromptStakes),
+}
+
+/// How the POS should pace an allowed prompt. The POS never cuts the host off
+/// sooner than the latency band (or the measured latency) allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacingDirective {
+    /// Use the normal flow, with a timeout of at least `latency_ms_max`.
+    Normal,
+    /// The flow is quicker than the host's comfort band; leave at least
+    /// `min_interval_ms` between steps instead of rushing.
+    SlowDown { min_interval_ms: u64 },
+    /// The host measurably needs longer than `latency_ms_max`; wait at least
+    /// `timeout_ms` before treating the prompt as unanswered.
+    ExtendTimeout { timeout_ms: u64 },
+}
+
+/// Handle for a prompt being shown, from `begin_prompt`. Not `Clone`:
+/// `end_prompt` consumes it, so one answer is never recorded twice.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PromptTicket {
+    pub prompt_id: u64,
+    pub stakes: PromptStakes,
+    pub started_at: SystemTime,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PromptDecision {
-    Allow,
-    DeferSoft, // ask AI companion to queue / delay
+    Allow { pacing: PacingDirective },
+    /// Ask AI companion to queue (see `crate::deferred_queue`) / delay; re-prompt
+    /// once after `retry_after`. `budget` is set when an hourly budget, rather
+    /// than the host's state or latency, is what blocked the prompt.
+    DeferSoft {
+        retry_after: Duration,
+        budget: Option<PromptBudget>,
+    },
     DenyHard,  // do not prompt (rights or overload)
 }
 
+/// POS prompt guard for one host's wallet; see `PromptState`.
 pub struct AugCitizenPosGuard<'a> {
     pub corridor: &'a AugFingerprintCorridor,
     pub state: PromptState,
+    /// Wallet-side corridor tracker, when the companion shares it with the POS.
+    pub stability: Option<&'a StabilityTracker>,
+    /// Least dwell inside the corridor before a prompt; set with `with_stability`.
+    pub min_stability_time: Duration,
+    /// Source of `now` for `now` / `should_prompt_now`; the system clock by default.
+    pub clock: &'a dyn Clock,
 }
 
 impl<'a> AugCitizenPosGuard<'a> {
     pub fn new(corridor: &'a AugFingerprintCorridor, state: PromptState) -> Self {
-        Self { corridor, state }
+        Self {
+            corridor,
+            state,
+            stability: None,
+            min_stability_time: Duration::ZERO,
+            clock: &SystemClock,
+        }
     }
 
-    pub fn reset_hour_window(&mut self) {
-        self.state.prompts_issued_this_hour = 0;
-        self.state.hour_window_started_at = Instant::now();
+    /// Consult the same debounced S_t / L_t corridor status as the wallet guard,
+    /// and the same dwell: pass the wallet's
+    /// `shard.consent_policy().min_stability_time()`.
+    pub fn with_stability(
+        mut self,
+        stability: &'a StabilityTracker,
+        min_stability_time: Duration,
+    ) -> Self {
+        self.stability = Some(stability);
+        self.min_stability_time = min_stability_time;
+        self
     }
 
-    /// Should the POS present a new payment prompt now, given expected latency?
-    pub fn should_prompt(&self, expected_latency_ms: u64) -> PromptDecision {
+    /// Read the time from `clock` instead of the system clock, e.g. a
+    /// `crate::clock::SimulatedClock` shared with the wallet guard.
+    pub fn with_clock(mut self, clock: &'a dyn Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The guard's current time; pass it to `record_prompt` / `begin_prompt` /
+    /// `end_prompt` so every window is kept on one clock.
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    /// Record that a prompt of class `stakes` was actually presented to the host at `now`.
+    pub fn record_prompt(&mut self, stakes: PromptStakes, now: SystemTime) {
+        self.state.prompts.record(now);
+        self.state.stakes_window_mut(stakes).record(now);
+    }
+
+    /// `record_prompt`, and start timing the host's answer.
+    pub fn begin_prompt(&mut self, stakes: PromptStakes, now: SystemTime) -> PromptTicket {
+        self.record_prompt(stakes, now);
+        let prompt_id = self.state.next_prompt_id;
+        self.state.next_prompt_id = self.state.next_prompt_id.wrapping_add(1);
+        PromptTicket {
+            prompt_id,
+            stakes,
+            started_at: now,
+        }
+    }
+
+    /// The host answered the prompt at `now`; adds the latency to the wallet's
+    /// distribution and returns it in ms (feed it to `record_episode` as
+    /// `consent_latency_ms`). A prompt the host never answered is simply not ended.
+    pub fn end_prompt(&mut self, ticket: PromptTicket, now: SystemTime) -> u64 {
+        let elapsed = now.duration_since(ticket.started_at).unwrap_or(Duration::ZERO);
+        let latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.state.latency.record(latency_ms);
+        latency_ms
+    }
+
+    /// Should the POS present a new prompt of class `stakes` at `now`, given
+    /// expected latency?
+    pub fn should_prompt_at(
+        &self,
+        stakes: PromptStakes,
+        expected_latency_ms: u64,
+        now: SystemTime,
+    ) -> PromptDecision {
         // 1. If not an organically-integrated augmented citizen, fallback.
-        if self.corridor.austatus != "organicallyintegratedaugmentedcitizen" {
-            return PromptDecision::Allow;
+        if !self.corridor.applies() {
+            return PromptDecision::Allow {
+                pacing: PacingDirective::Normal,
+            };
         }
 
-        // 2. Enforce max prompts per hour (already merchant-tightened).
-        if self.state.prompts_issued_this_hour >= self.corridor.max_prompts_per_hour {
-            return PromptDecision::DeferSoft;
+        // 2. Never prompt while the host is outside the shared neuro corridor or
+        //    leaving it; the exit debounce is the least it takes to settle either way.
+        //    Inside, wait out the same minimum dwell the wallet guard requires.
+        if let Some(stability) = self.stability {
+            if !stability.is_inside() || stability.exit_pending() {
+                return self.defer_soft(stakes, stability.exit_debounce, None, now);
+            }
+            let dwell = stability.dwell(now).unwrap_or(Duration::ZERO);
+            if dwell < self.min_stability_time {
+                return self.defer_soft(stakes, self.min_stability_time - dwell, None, now);
+            }
         }
 
-        // 3. Enforce latency comfort band to avoid coercive timeouts.
+        // 3. Enforce max prompts per hour (already merchant-tightened), then the
+        //    class's own budget so low-stakes prompts can't use up high-stakes room.
+        if self.state.prompts.count(now) >= self.corridor.max_prompts_per_hour {
+            return self.defer_soft(stakes, Duration::ZERO, Some(PromptBudget::AllPrompts), now);
+        }
+        if self.state.stakes_window(stakes).count(now) >= self.corridor.stakes_budget(stakes) {
+            let budget = Some(PromptBudget::Stakes(stakes));
+            return self.defer_soft(stakes, Duration::ZERO, budget, now);
+        }
+
+        // 4. Enforce latency comfort band to avoid coercive timeouts: a prompt
+        //    expected to take too long waits; a host who measurably answers slower
+        //    gets a longer timeout from `pacing` instead.
         if expected_latency_ms > self.corridor.latency_ms_max {
-            return PromptDecision::DeferSoft;
+            return self.defer_soft(stakes, Duration::ZERO, None, now);
         }
 
-        // Below min: allow, but AI companion should pace UX.
-        if expected_latency_ms < self.corridor.latency_ms_min {
-            return PromptDecision::Allow;
+        // 5. Pace the prompt to the host's measured latency.
+        PromptDecision::Allow {
+            pacing: self.pacing(expected_latency_ms),
         }
+    }
+
+    /// `should_prompt_at` at the guard clock's `now`.
+    pub fn should_prompt_now(
+        &self,
+        stakes: PromptStakes,
+        expected_latency_ms: u64,
+    ) -> PromptDecision {
+        self.should_prompt_at(stakes, expected_latency_ms, self.now())
+    }
+
+    /// Should the POS present a new payment prompt now? Kept for callers written
+    /// before prompts had stakes classes. The payment is unclassified, so it is
+    /// checked as `PromptStakes::HighStakes` (against `max_decisions_per_hour`),
+    /// at the guard clock's `now`.
+    #[deprecated(note = "use should_prompt_at / should_prompt_now with the prompt's PromptStakes")]
+    pub fn should_prompt(&self, expected_latency_ms: u64) -> PromptDecision {
+        self.should_prompt_now(PromptStakes::HighStakes, expected_latency_ms)
+    }
+
+    /// Kept for callers written before the prompt budget became a sliding
+    /// window; prompts now age out on their own and there is nothing to reset.
+    #[deprecated(note = "prompt budgets are sliding one-hour windows; no reset is needed")]
+    pub fn reset_hour_window(&mut self) {}
+
+    /// Pacing for an allowed prompt. A host whose measured p90 latency runs past
+    /// the band gets a longer timeout (never a cut-off); a flow quicker than the
+    /// band's floor, expected or measured, is slowed down.
+    pub fn pacing(&self, expected_latency_ms: u64) -> PacingDirective {
+        let latency = &self.state.latency;
+        if let Some(p90) = latency.p90_ms() {
+            let needed = p90.saturating_add(p90 / 2);
+            if needed > self.corridor.latency_ms_max {
+                return PacingDirective::ExtendTimeout { timeout_ms: needed };
+            }
+        }
+        let typical = latency.median_ms().unwrap_or(expected_latency_ms);
+        let floor = self.corridor.latency_ms_min;
+        if expected_latency_ms < floor || typical < floor {
+            return PacingDirective::SlowDown {
+                min_interval_ms: floor,
+            };
+        }
+        PacingDirective::Normal
+    }
+
+    /// `DeferSoft` with a retry hint: at least `wait`, the next slot free in both
+    /// the overall and the `stakes` budget, and the top of the latency band.
+    fn defer_soft(
+        &self,
+        stakes: PromptStakes,
+        wait: Duration,
+        budget: Option<PromptBudget>,
+        now: SystemTime,
+    ) -> PromptDecision {
+        let overall = self
+            .state
+            .prompts
+            .next_slot_at(now, self.corridor.max_prompts_per_hour);
+        let class = self
+            .state
+            .stakes_window(stakes)
+            .next_slot_at(now, self.corridor.stakes_budget(stakes));
+        let next_slot = overall.max(class);
+        PromptDecision::DeferSoft {
+            retry_after: retry_hint(wait, next_slot, self.corridor.latency_ms_max, now),
+            budget,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::augfingerprint_corridor::AuStatus;
+    use crate::clock::FixedClock;
+    use crate::test_support::{at, neuro_corridor, pos_corridor as corridor, sample};
+
+    const MIN_STABILITY: Duration = Duration::from_secs(5);
+
+    /// Tracker fed `(svalue, t)` samples in order.
+    fn tracker(samples: &[(f32, u64)]) -> StabilityTracker {
+        let c = neuro_corridor();
+        let mut t = StabilityTracker::default();
+        for &(svalue, secs) in samples {
+            t.observe(&c, &sample(svalue, secs));
+        }
+        t
+    }
+
+    fn is_allow(decision: PromptDecision) -> bool {
+        matches!(decision, PromptDecision::Allow { .. })
+    }
+
+    #[test]
+    fn hosts_outside_the_corridor_scheme_are_always_allowed() {
+        let mut c = corridor();
+        c.austatus = AuStatus::NonAugmented;
+        let outside = tracker(&[(0.9, 0)]);
+        let mut guard = AugCitizenPosGuard::new(&c, PromptState::new())
+            .with_stability(&outside, MIN_STABILITY);
+        for _ in 0..20 {
+            guard.record_prompt(PromptStakes::HighStakes, at(1));
+        }
+        let decision = guard.should_prompt_at(PromptStakes::HighStakes, 10_000, at(1));
+        assert_eq!(decision, PromptDecision::Allow { pacing: PacingDirective::Normal });
+    }
+
+    #[test]
+    fn defers_while_the_host_is_outside_the_neuro_corridor() {
+        let c = corridor();
+        let outside = tracker(&[(0.9, 0)]);
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new())
+            .with_stability(&outside, MIN_STABILITY);
+        match guard.should_prompt_at(PromptStakes::Informational, 1_000, at(1)) {
+            PromptDecision::DeferSoft { retry_after, budget } => {
+                assert!(retry_after >= outside.exit_debounce);
+                assert_eq!(budget, None);
+            }
+            other => panic!("expected DeferSoft, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defers_while_a_corridor_exit_is_pending() {
+        let c = corridor();
+        let pending = tracker(&[(0.5, 0), (0.98, 10)]);
+        assert!(pending.is_inside() && pending.exit_pending());
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new())
+            .with_stability(&pending, MIN_STABILITY);
+        let decision = guard.should_prompt_at(PromptStakes::Informational, 1_000, at(10));
+        assert!(matches!(decision, PromptDecision::DeferSoft { budget: None, .. }));
+
+        // Back inside before the debounce ran out: prompting resumes.
+        let settled = tracker(&[(0.5, 0), (0.98, 10), (0.5, 11)]);
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new())
+            .with_stability(&settled, MIN_STABILITY);
+        assert!(is_allow(guard.should_prompt_at(PromptStakes::Informational, 1_000, at(11))));
+    }
+
+    #[test]
+    fn defers_until_the_host_has_dwelt_in_the_corridor() {
+        let c = corridor();
+        let entered = tracker(&[(0.9, 0), (0.5, 10)]);
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new())
+            .with_stability(&entered, MIN_STABILITY);
+        match guard.should_prompt_at(PromptStakes::Informational, 1_000, at(11)) {
+            PromptDecision::DeferSoft { retry_after, budget } => {
+                assert_eq!(retry_after, Duration::from_secs(4));
+                assert_eq!(budget, None);
+            }
+            other => panic!("expected DeferSoft, got {:?}", other),
+        }
+        assert!(is_allow(guard.should_prompt_at(PromptStakes::Informational, 1_000, at(15))));
+    }
+
+    #[test]
+    fn expected_latency_over_the_band_defers() {
+        let c = corridor();
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new());
+        assert!(is_allow(guard.should_prompt_at(PromptStakes::Informational, 3_000, at(0))));
+        let decision = guard.should_prompt_at(PromptStakes::Informational, 3_001, at(0));
+        assert!(matches!(decision, PromptDecision::DeferSoft { budget: None, .. }));
+    }
+
+    #[test]
+    fn a_spent_class_budget_blocks_only_that_class() {
+        let c = corridor();
+        let mut guard = AugCitizenPosGuard::new(&c, PromptState::new());
+        for _ in 0..c.max_decisions_per_hour {
+            guard.record_prompt(PromptStakes::HighStakes, at(0));
+        }
+        match guard.should_prompt_at(PromptStakes::HighStakes, 1_000, at(100)) {
+            PromptDecision::DeferSoft { retry_after, budget } => {
+                assert_eq!(budget, Some(PromptBudget::Stakes(PromptStakes::HighStakes)));
+                // The oldest high-stakes prompt ages out at 3_600.
+                assert_eq!(retry_after, Duration::from_secs(3_500));
+            }
+            other => panic!("expected DeferSoft, got {:?}", other),
+        }
+        assert!(is_allow(guard.should_prompt_at(PromptStakes::LowValuePayment, 1_000, at(100))));
+        assert!(is_allow(guard.should_prompt_at(PromptStakes::HighStakes, 1_000, at(3_600))));
+    }
+
+    #[test]
+    fn every_class_counts_against_the_overall_budget() {
+        let c = corridor();
+        let mut guard = AugCitizenPosGuard::new(&c, PromptState::new());
+        for _ in 0..5 {
+            guard.record_prompt(PromptStakes::Informational, at(0));
+            guard.record_prompt(PromptStakes::LowValuePayment, at(0));
+        }
+        assert_eq!(guard.state.prompts_last_hour(at(0)), c.max_prompts_per_hour);
+        let decision = guard.should_prompt_at(PromptStakes::HighStakes, 1_000, at(1));
+        assert!(matches!(
+            decision,
+            PromptDecision::DeferSoft { budget: Some(PromptBudget::AllPrompts), .. }
+        ));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_should_prompt_checks_the_high_stakes_budget() {
+        let c = corridor();
+        let clock = FixedClock(at(0));
+        let mut guard = AugCitizenPosGuard::new(&c, PromptState::new()).with_clock(&clock);
+        // Informational prompts leave the decision budget alone.
+        for _ in 0..c.max_informational_per_hour {
+            guard.record_prompt(PromptStakes::Informational, at(0));
+        }
+        assert!(is_allow(guard.should_prompt(1_000)));
+        for _ in 0..c.max_decisions_per_hour {
+            guard.record_prompt(PromptStakes::HighStakes, at(0));
+        }
+        guard.reset_hour_window();
+        let decision = guard.should_prompt(1_000);
+        assert!(matches!(
+            decision,
+            PromptDecision::DeferSoft {
+                budget: Some(PromptBudget::Stakes(PromptStakes::HighStakes)),
+                ..
+            }
+        ));
+    }
+
+    fn stats(samples: &[u64]) -> LatencyStats {
+        let mut stats = LatencyStats::default();
+        for &ms in samples {
+            stats.record(ms);
+        }
+        stats
+    }
+
+    #[test]
+    fn percentiles_need_enough_samples() {
+        assert_eq!(stats(&[900; MIN_LATENCY_SAMPLES - 1]).median_ms(), None);
+        let s = stats(&[100, 200, 300, 400, 500, 600, 700, 800, 900, 1_000]);
+        assert_eq!(s.median_ms(), Some(500));
+        assert_eq!(s.p90_ms(), Some(900));
+        assert_eq!(s.percentile_ms(100), Some(1_000));
+    }
+
+    #[test]
+    fn only_the_latest_samples_are_kept() {
+        let mut s = stats(&[10_000; LATENCY_WINDOW]);
+        for _ in 0..LATENCY_WINDOW {
+            s.record(1_000);
+        }
+        assert_eq!(s.len(), LATENCY_WINDOW);
+        assert_eq!(s.p90_ms(), Some(1_000));
+    }
+
+    #[test]
+    fn a_prompt_is_timed_from_begin_to_end() {
+        let c = corridor();
+        let mut guard = AugCitizenPosGuard::new(&c, PromptState::new());
+        let first = guard.begin_prompt(PromptStakes::LowValuePayment, at(0));
+        let second = guard.begin_prompt(PromptStakes::Informational, at(1));
+        assert_ne!(first.prompt_id, second.prompt_id);
+        assert_eq!(guard.state.prompts_last_hour(at(1)), 2);
+        assert_eq!(guard.state.stakes_window(PromptStakes::LowValuePayment).count(at(1)), 1);
+
+        assert_eq!(guard.end_prompt(first, at(2)), 2_000);
+        // An answer stamped before the prompt (clock step) counts as instant.
+        assert_eq!(guard.end_prompt(second, at(0)), 0);
+        assert_eq!(guard.state.latency.len(), 2);
+    }
+
+    #[test]
+    fn a_slow_host_gets_a_longer_timeout_never_a_cut_off() {
+        let c = corridor();
+        let mut state = PromptState::new();
+        state.latency = stats(&[2_000, 2_500, 2_800, 3_000, 3_000]);
+        let guard = AugCitizenPosGuard::new(&c, state);
+        let decision = guard.should_prompt_at(PromptStakes::LowValuePayment, 1_000, at(0));
+        let pacing = PacingDirective::ExtendTimeout { timeout_ms: 4_500 };
+        assert_eq!(decision, PromptDecision::Allow { pacing });
+    }
+
+    #[test]
+    fn a_flow_quicker_than_the_band_is_slowed_down() {
+        let c = corridor();
+        let slow_down = PacingDirective::SlowDown { min_interval_ms: 500 };
+        let guard = AugCitizenPosGuard::new(&c, PromptState::new());
+        assert_eq!(guard.pacing(400), slow_down);
+        assert_eq!(guard.pacing(1_000), PacingDirective::Normal);
 
-        PromptDecision::Allow
+        // A host who measurably answers quicker than the floor is paced as well.
+        let mut state = PromptState::new();
+        state.latency = stats(&[300; MIN_LATENCY_SAMPLES]);
+        let guard = AugCitizenPosGuard::new(&c, state);
+        assert_eq!(guard.pacing(1_000), slow_down);
     }
 }
@@ -1,5 +1,19 @@
 use std::time::{Duration, SystemTime};
 
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::audit_redaction::{AuditRedaction, InnerStateRecord};
+use crate::caregiver_coapproval::{CaregiverDelegation, PendingApproval, DEFAULT_COAPPROVAL_TIMEOUT};
+use crate::clock::Clock;
+use crate::consent_episode::EpisodeRecorder;
+use crate::consent_fusion::{ChannelWeights, FusedConsent};
+use crate::consent_policy::{ConsentPolicy, PolicyContext, PolicyThresholds};
+use crate::consent_recovery::{SuspensionAuditRecord, SuspensionState, DEFAULT_RECOVERY_COOLDOWN};
+use crate::deferred_queue::DeferredQueue;
+use crate::neuro_corridor::{CorridorBand, NeuroCorridor, StabilityTracker};
+use crate::rate_window::SlidingWindow;
+
 /// Ecosafety / knowledge / risk scalar used with Paycomp-style KER scoring.
 /// K in [0,1], E in [0,1], R in [0,1].
 #[derive(Debug, Clone, Copy)]
@@ -17,21 +31,23 @@ pub struct NeuroState {
     pub svalue: f32,
     /// Normalized organiccpu load L_t ∈ [0,1].
     pub loadvalue: f32,
-    /// Lower bound of safe consent corridor for S_t.
+    /// Lower bound of the safe consent corridor for S_t (entry band).
     pub smin: f32,
-    /// Upper bound of safe consent corridor for S_t.
+    /// Upper bound of the safe consent corridor for S_t (entry band).
     pub smax: f32,
-    /// Max allowed organiccpu load inside safe band.
+    /// Max allowed organiccpu load inside safe band (entry band).
     pub loadmax: f32,
     /// Last time this state snapshot was updated by the AI-companion.
     pub last_update: SystemTime,
 }
 
 /// Policy for AI consent behavior.
+/// Each variant selects a built-in `crate::consent_policy::ConsentPolicy`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AiConsentPolicy {
     Conservative,
     Balanced,
+    CaregiverCoapproval,
 }
 
 /// Control mode for interaction.
@@ -44,15 +60,44 @@ pub enum ControlMode {
 }
 
 /// Result of a consent evaluation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConsentDecision {
     Allow,
     Deny,
     Defer,
 }
 
-/// High-level reason codes for logging and analytics.
+/// Which consent outcomes are charged against `max_prompts_per_hour`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptAccounting {
+    /// Only allowed payments consume prompt budget (pre-accounting behavior).
+    AllowOnly,
+    /// Allowed and deferred prompts count; denials do not.
+    AllowAndDefer,
+    /// Every prompt the host experienced counts, whatever the outcome.
+    AllSurfaced,
+}
+
+impl PromptAccounting {
+    /// Whether an evaluation ending in `decision` / `reason` is charged as a prompt.
+    ///
+    /// A `PromptRateExceeded` denial is never charged: no prompt was surfaced, and
+    /// charging it would let repeated taps extend the lockout indefinitely.
+    pub fn counts(self, decision: ConsentDecision, reason: ConsentReason) -> bool {
+        if reason == ConsentReason::PromptRateExceeded {
+            return false;
+        }
+        match self {
+            PromptAccounting::AllowOnly => decision == ConsentDecision::Allow,
+            PromptAccounting::AllowAndDefer => decision != ConsentDecision::Deny,
+            PromptAccounting::AllSurfaced => true,
+        }
+    }
+}
+
+/// High-level reason codes for logging and analytics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConsentReason {
     Ok,
     ConsentSuspended,
@@ -62,8 +107,44 @@ pub enum ConsentReason {
     StateOutsideCorridor,
     EssentialStateUnstable,
     StabilityTimeInsufficient,
+    /// The evaluation time is before the corridor entry (clock skew).
+    StabilityTimeInvalid,
     StabilityNotYetEstablished,
+    NeuroStateStale,
     RiskScoresTooHigh,
+    CaregiverApprovalRequired,
+    CaregiverUnavailable,
+    CaregiverDeclined,
+    AiConsentNotConfirmed,
+    DailySpendExceeded,
+    /// The latest sample left the exit band; the exit is still being debounced.
+    CorridorExitPending,
+}
+
+impl ConsentReason {
+    /// Stable snake_case code used by audit pipelines and the legacy string API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConsentReason::Ok => "ok",
+            ConsentReason::ConsentSuspended => "consent_suspended",
+            ConsentReason::PromptRateExceeded => "prompt_rate_exceeded",
+            ConsentReason::PaymentRateExceeded => "payment_rate_exceeded",
+            ConsentReason::AmountOverLimit => "amount_over_limit",
+            ConsentReason::StateOutsideCorridor => "state_outside_corridor",
+            ConsentReason::EssentialStateUnstable => "essential_state_unstable",
+            ConsentReason::StabilityTimeInsufficient => "stability_time_insufficient",
+            ConsentReason::StabilityTimeInvalid => "stability_time_invalid",
+            ConsentReason::StabilityNotYetEstablished => "stability_not_yet_established",
+            ConsentReason::NeuroStateStale => "neuro_state_stale",
+            ConsentReason::RiskScoresTooHigh => "risk_scores_too_high",
+            ConsentReason::CaregiverApprovalRequired => "caregiver_approval_required",
+            ConsentReason::CaregiverUnavailable => "caregiver_unavailable",
+            ConsentReason::CaregiverDeclined => "caregiver_declined",
+            ConsentReason::AiConsentNotConfirmed => "ai_consent_not_confirmed",
+            ConsentReason::DailySpendExceeded => "daily_spend_exceeded",
+            ConsentReason::CorridorExitPending => "corridor_exit_pending",
+        }
+    }
 }
 
 /// AugFingerprint shard for an implanted-NFC, internal-state–controlled wallet.
@@ -91,25 +172,58 @@ pub struct AugFingerprintShard {
     pub oculus_cortex_calibrated: bool,
     pub latency_profile: String,    // "spiky"
     pub max_cognitive_load: f32,    // safe load ceiling, e.g. 0.4
+    /// Comfortable consent round-trip band; retry hints never undercut the top.
+    pub latency_ms_min: u64,
+    pub latency_ms_max: u64,
+    /// Reliability per consent channel for `ControlMode::Mixed`; see `crate::consent_fusion`.
+    pub channel_weights: ChannelWeights,
+    /// Fused confidence at or above which the channels count as a confirm.
+    pub fusion_min_confidence: f32,
 
     // Spending corridors (microunits: 1 = 0.001 USD)
     pub max_auto_amount_mills: u64,
     pub max_daily_spend_mills: u64,
     pub max_payments_per_hour: u32,
     pub max_prompts_per_hour: u32,
+    pub prompt_accounting: PromptAccounting,
 
     // Quantified-learning / consent parameters
     pub learning_mode: String,      // "quantifiedlaneswitching"
     pub ai_consent_policy: AiConsentPolicy,
-    pub min_stability_time: Duration,
-    pub consent_suspended: bool,
+    /// Risk ceilings and stability time handed to the selected policy.
+    pub policy_thresholds: PolicyThresholds,
+    /// Suspension lifecycle; see `crate::consent_recovery`.
+    pub suspension: SuspensionState,
+    /// Time S_t / L_t must stay in corridor before a suspension lifts by itself.
+    pub recovery_cooldown: Duration,
+    /// Oldest `neuro_state.last_update` a decision may still be based on.
+    pub neuro_state_max_age: Duration,
+    /// How far the exit band sits outside the S_t entry bounds.
+    pub corridor_s_hysteresis: f32,
+    /// How far the exit band sits above the load ceiling.
+    pub corridor_load_hysteresis: f32,
+
+    // Caregiver co-approval; see `crate::caregiver_coapproval`.
+    pub caregiver: Option<CaregiverDelegation>,
+    /// Time the caregiver has to co-sign before a pending approval lapses.
+    pub coapproval_timeout: Duration,
+    /// Amount above which even essential payments need the caregiver.
+    pub coapproval_threshold_mills: u64,
+    pub pending_approvals: Vec<PendingApproval>,
+    pub next_approval_id: u64,
+    /// Deferred payments awaiting replay; see `crate::deferred_queue`.
+    pub deferred_queue: DeferredQueue,
 
     // Dynamic state
     pub neuro_state: NeuroState,
-    pub stable_since: Option<SystemTime>,
-    pub payments_last_hour: u32,
-    pub prompts_last_hour: u32,
-    pub last_reset_window: SystemTime,
+    /// Debounced corridor status and dwell time, fed by `update_neuro_state`.
+    pub stability: StabilityTracker,
+    /// Allowed payments in the sliding one-hour window.
+    pub payments_window: SlidingWindow,
+    /// Prompts surfaced to the host in the sliding one-hour window.
+    pub prompts_window: SlidingWindow,
+    /// Allowed spend (weighted in mills) in the sliding 24h window.
+    pub spend_window: SlidingWindow,
 
     // Risk / eco scoring
     pub ker_score: KerScore,
@@ -120,6 +234,13 @@ pub struct AugFingerprintShard {
 
     // Audit
     pub consent_audit_log_enabled: bool,
+    /// How S_t / L_t appear in audit records; see `crate::audit_redaction`.
+    pub audit_redaction: AuditRedaction,
+    /// Host-held secret for `AuditRedaction::Commitments`; never loaded from ALN.
+    pub audit_salt: Option<Vec<u8>>,
+    pub suspension_log: Vec<SuspensionAuditRecord>,
+    /// Host feedback per decision; see `crate::consent_episode`.
+    pub consent_episodes: EpisodeRecorder,
 }
 
 /// Default constructor for a profile like yours:
@@ -142,16 +263,32 @@ impl AugFingerprintShard {
             oculus_cortex_calibrated: false,
             latency_profile: "spiky".to_string(),
             max_cognitive_load: 0.4,
+            latency_ms_min: 500,
+            latency_ms_max: 3_000,
+            channel_weights: ChannelWeights::DEFAULT,
+            fusion_min_confidence: 0.7,
 
             max_auto_amount_mills: 50_000,   // 50.000 USD
             max_daily_spend_mills: 200_000,  // 200.000 USD
             max_payments_per_hour: 6,
             max_prompts_per_hour: 10,
+            prompt_accounting: PromptAccounting::AllSurfaced,
 
             learning_mode: "quantifiedlaneswitching".to_string(),
             ai_consent_policy: AiConsentPolicy::Conservative,
-            min_stability_time: Duration::from_secs(5),
-            consent_suspended: false,
+            policy_thresholds: PolicyThresholds::CONSERVATIVE,
+            suspension: SuspensionState::Active,
+            recovery_cooldown: DEFAULT_RECOVERY_COOLDOWN,
+            neuro_state_max_age: Duration::from_secs(30),
+            corridor_s_hysteresis: 0.05,
+            corridor_load_hysteresis: 0.05,
+
+            caregiver: None,
+            coapproval_timeout: DEFAULT_COAPPROVAL_TIMEOUT,
+            coapproval_threshold_mills: 50_000,
+            pending_approvals: Vec::new(),
+            next_approval_id: 1,
+            deferred_queue: DeferredQueue::new(),
 
             neuro_state: NeuroState {
                 svalue: 0.0,
@@ -161,10 +298,10 @@ impl AugFingerprintShard {
                 loadmax: 0.5,
                 last_update: now,
             },
-            stable_since: None,
-            payments_last_hour: 0,
-            prompts_last_hour: 0,
-            last_reset_window: now,
+            stability: StabilityTracker::default(),
+            payments_window: SlidingWindow::hourly(),
+            prompts_window: SlidingWindow::hourly(),
+            spend_window: SlidingWindow::daily(),
 
             ker_score: KerScore {
                 knowledge: 0.9,
@@ -177,24 +314,88 @@ impl AugFingerprintShard {
             e_accessibility: 0.9,
 
             consent_audit_log_enabled: true,
+            audit_redaction: AuditRedaction::Bands,
+            audit_salt: None,
+            suspension_log: Vec::new(),
+            consent_episodes: EpisodeRecorder::new(),
         }
     }
 
     /// Called by the AI-companion whenever a new internal state has been computed.
-    pub fn update_neuro_state(&mut self, state: NeuroState) {
+    /// Also accepts the legacy `crate::paycomp::aug_fingerprint_guard::NeuroState`.
+    pub fn update_neuro_state(&mut self, state: impl Into<NeuroState>) {
+        let state = state.into();
+        // A reporting gap longer than the freshness bound breaks the stability run:
+        // nothing vouches for the corridor while the companion was silent.
+        let gap_too_long = self.stability.last_observed().is_some_and(|t| {
+            state
+                .last_update
+                .duration_since(t)
+                .is_ok_and(|gap| gap > self.neuro_state_max_age)
+        });
+        if gap_too_long {
+            self.stability.restart();
+            self.restart_recovery(state.last_update);
+        }
         self.neuro_state = state;
+        let corridor = self.corridor();
+        self.stability.observe(&corridor, &state);
+        self.poll_recovery(state.last_update);
     }
 
-    /// Reset hourly counters when needed (sliding one-hour window).
-    pub fn reset_counters_if_needed(&mut self, now: SystemTime) {
-        if let Ok(delta) = now.duration_since(self.last_reset_window) {
-            if delta >= Duration::from_secs(3600) {
-                self.payments_last_hour = 0;
-                self.prompts_last_hour = 0;
-                self.last_reset_window = now;
-            }
+    /// Start of the current in-corridor stay, as seen from the companion's stream.
+    pub fn stable_since(&self) -> Option<SystemTime> {
+        self.stability.entered_at()
+    }
+
+    /// Hysteresis corridor built from the current entry bounds and the shard margins.
+    /// The load ceiling is the tighter of the companion's `loadmax` and `max_cognitive_load`.
+    pub fn corridor(&self) -> NeuroCorridor {
+        let ns = self.neuro_state;
+        let entry = CorridorBand {
+            smin: ns.smin,
+            smax: ns.smax,
+            loadmax: ns.loadmax.min(self.max_cognitive_load),
+        };
+        NeuroCorridor::with_hysteresis(entry, self.corridor_s_hysteresis, self.corridor_load_hysteresis)
+    }
+
+    /// Whether the companion reported within `neuro_state_max_age` of `now`.
+    /// A snapshot stamped after `now` (clock skew) counts as fresh.
+    pub fn neuro_state_fresh(&self, now: SystemTime) -> bool {
+        match now.duration_since(self.neuro_state.last_update) {
+            Ok(age) => age <= self.neuro_state_max_age,
+            Err(_) => true,
         }
     }
+
+    /// Whether S_t / L_t are inside the safe corridor, with hysteresis and exit debounce.
+    ///
+    /// Still true while an exit is being debounced; the guard defers on
+    /// `stability.exit_pending()` on top of this.
+    pub fn within_corridor(&self) -> bool {
+        self.stability.is_inside()
+    }
+
+    /// Prompts surfaced in the hour ending at `now`.
+    pub fn prompts_last_hour(&self, now: SystemTime) -> u32 {
+        self.prompts_window.count(now)
+    }
+
+    /// Payments allowed in the hour ending at `now`.
+    pub fn payments_last_hour(&self, now: SystemTime) -> u32 {
+        self.payments_window.count(now)
+    }
+
+    /// Total allowed spend in the rolling 24h window ending at `now`.
+    pub fn daily_spend_mills(&self, now: SystemTime) -> u64 {
+        self.spend_window.total(now)
+    }
+
+    /// No-op kept for callers of the fixed-hour counters; the sliding windows
+    /// expire entries on their own.
+    #[deprecated(note = "rate windows roll continuously; use payments_last_hour / prompts_last_hour")]
+    pub fn reset_counters_if_needed(&mut self, _now: SystemTime) {}
 }
 
 /// Payment request as seen by the guard at POS / XR / agent.
@@ -209,7 +410,7 @@ pub struct PaymentRequest {
 
 /// External consent status from the AI-companion.
 /// This is the macro-state “CONFIRMED / DENY / SUSPENDED” the POS can see.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AiConsentState {
     Confirmed,
     Deny,
@@ -218,25 +419,59 @@ pub enum AiConsentState {
 }
 
 /// Audit record for a consent decision.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsentAuditRecord {
+    /// Opaque id shared with the merchant via `crate::merchant_view::MerchantDecisionView`.
+    pub decision_id: Uuid,
     pub wallet_did: String,
     pub merchant_id: String,
     pub amount_mills: u64,
     pub decision: ConsentDecision,
     pub reason: ConsentReason,
     pub timestamp: SystemTime,
-    pub s_value: f32,
-    pub load_value: f32,
-    pub ai_consent_state: AiConsentState,
+    /// S_t / L_t evidence, redacted per the shard's `audit_redaction`.
+    pub inner_state: InnerStateRecord,
+    /// Macro-state reported by the AI-companion, if that channel is in use.
+    pub ai_consent_state: Option<AiConsentState>,
+    /// Caregiver approval this record opened or settled.
+    pub approval_id: Option<u64>,
+    /// Caregiver who co-signed or declined; the host is `wallet_did`.
+    pub co_approver_did: Option<String>,
+    /// Fused confidence and per-channel evidence, for `ControlMode::Mixed`.
+    pub fusion: Option<FusedConsent>,
+}
+
+/// Full result of an `AugFingerprintGuard` evaluation, retry hint included.
+///
+/// Returned by the `*_outcome` / `*_with_hint` entry points; the original entry
+/// points return `into_parts()`.
+#[derive(Debug, Clone)]
+pub struct ConsentOutcome {
+    pub decision: ConsentDecision,
+    pub reason: ConsentReason,
+    /// Recommended wait before re-prompting; `Some` exactly when `decision` is
+    /// `Defer`. See `crate::retry_hint`.
+    pub retry_after: Option<Duration>,
+    pub audit: Option<ConsentAuditRecord>,
+}
+
+impl ConsentOutcome {
+    /// The `(decision, reason, audit)` triple, dropping the retry hint.
+    pub fn into_parts(self) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
+        (self.decision, self.reason, self.audit)
+    }
 }
 
 /// Core guard enforcing the internal-state corridor consent model.
 ///
 /// Integration points:
-/// - POS calls `evaluate_payment` after NFC tap and before authorization.
+/// - POS calls `evaluate_payment` after NFC tap and before authorization, or
+///   `evaluate_with_hint` to also get the retry hint for a `Defer`.
 /// - XR / oculus flow calls the same guard before confirming visual totals.
-/// - Merchant risk engines only see decision + Reason, never raw internal signals.
+/// - Merchant risk engines only see decision + Reason, never raw internal signals;
+///   hand them `crate::merchant_view::MerchantDecisionView`, not the audit record.
+/// - `crate::paycomp::aug_fingerprint_guard` wraps `evaluate` for callers that have
+///   no AI-companion macro-state channel.
 pub struct AugFingerprintGuard;
 
 impl AugFingerprintGuard {
@@ -246,202 +481,400 @@ impl AugFingerprintGuard {
     /// - Never treat silence / missing consent as YES.
     /// - Respect hourly prompt/payment caps.
     /// - Enforce S_t and L_t corridor with minimum stability time.
-    /// - Bias toward under-paying (Deny/Defer) when risk metrics are high.
+    /// - Bias toward under-paying (Deny/Defer) when risk metrics are high; the
+    ///   shard's `ConsentPolicy` decides how, after the rights-level checks pass.
     pub fn evaluate_payment(
         shard: &mut AugFingerprintShard,
         request: &PaymentRequest,
         ai_state: AiConsentState,
     ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
-        shard.reset_counters_if_needed(request.now);
+        Self::evaluate_with_hint(shard, request, ai_state).into_parts()
+    }
+
+    /// `evaluate_payment`, returning the full `ConsentOutcome` with its retry hint.
+    pub fn evaluate_with_hint(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: AiConsentState,
+    ) -> ConsentOutcome {
+        Self::evaluate_outcome(shard, request, Some(ai_state))
+    }
+
+    /// `evaluate_payment` for an optional AI-companion macro-state.
+    ///
+    /// `ai_state` is `None` only when the deployment has no AI-companion macro-state
+    /// channel at all; the corridor and stability checks still gate consent then.
+    /// When the channel exists, anything other than `Confirmed` is never read as YES.
+    pub fn evaluate(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+    ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
+        Self::evaluate_outcome(shard, request, ai_state).into_parts()
+    }
+
+    /// `evaluate`, returning the full `ConsentOutcome`. Shared implementation
+    /// behind every AugFingerprint entry point.
+    pub fn evaluate_outcome(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+    ) -> ConsentOutcome {
+        let policy = shard.consent_policy();
+        Self::evaluate_outcome_with_policy(shard, request, ai_state, policy.as_ref())
+    }
+
+    /// `evaluate_outcome` with the request stamped at `clock.now()` instead of the
+    /// caller's `request.now`, so every window, deadline and audit timestamp of the
+    /// evaluation comes from one `Clock`.
+    pub fn evaluate_with_clock(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        clock: &dyn Clock,
+    ) -> ConsentOutcome {
+        let request = PaymentRequest {
+            now: clock.now(),
+            ..request.clone()
+        };
+        Self::evaluate_outcome(shard, &request, ai_state)
+    }
+
+    /// Same as `evaluate`, but with a caller-supplied `ConsentPolicy` instead of the
+    /// one selected by the shard's `ai_consent_policy`.
+    pub fn evaluate_with_policy(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        policy: &dyn ConsentPolicy,
+    ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
+        Self::evaluate_outcome_with_policy(shard, request, ai_state, policy).into_parts()
+    }
+
+    /// `evaluate_with_policy`, returning the full `ConsentOutcome`.
+    pub fn evaluate_outcome_with_policy(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        policy: &dyn ConsentPolicy,
+    ) -> ConsentOutcome {
+        let (decision, reason) = Self::decide(shard, request, ai_state, policy);
+        let approval_id = if reason == ConsentReason::CaregiverApprovalRequired {
+            shard.open_coapproval(request, ai_state)
+        } else {
+            None
+        };
+
+        if shard.prompt_accounting.counts(decision, reason) {
+            shard.prompts_window.record(request.now);
+        }
+        Self::settle(
+            shard,
+            request,
+            ai_state,
+            (decision, reason),
+            policy.min_stability_time(),
+            approval_id,
+            None,
+        )
+    }
+
+    /// Book an outcome (payment / spend windows), attach the retry hint for a
+    /// `Defer` and build the audit record.
+    pub(crate) fn settle(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        (decision, reason): (ConsentDecision, ConsentReason),
+        min_stability_time: Duration,
+        approval_id: Option<u64>,
+        co_approver_did: Option<String>,
+    ) -> ConsentOutcome {
+        if decision == ConsentDecision::Allow {
+            shard.payments_window.record(request.now);
+            shard.spend_window.record_weighted(request.now, request.amount_mills);
+        }
+
+        let audit = if shard.consent_audit_log_enabled {
+            Some(ConsentAuditRecord {
+                decision_id: Uuid::new_v4(),
+                wallet_did: shard.wallet_did.clone(),
+                merchant_id: request.merchant_id.clone(),
+                amount_mills: request.amount_mills,
+                decision,
+                reason,
+                timestamp: request.now,
+                inner_state: shard.audit_inner_state(request.now),
+                ai_consent_state: ai_state,
+                approval_id,
+                co_approver_did,
+                fusion: None,
+            })
+        } else {
+            None
+        };
+
+        let retry_after = if decision == ConsentDecision::Defer {
+            Some(shard.retry_after(reason, min_stability_time, request.now))
+        } else {
+            None
+        };
+
+        ConsentOutcome {
+            decision,
+            reason,
+            retry_after,
+            audit,
+        }
+    }
+
+    pub(crate) fn decide(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+        ai_state: Option<AiConsentState>,
+        policy: &dyn ConsentPolicy,
+    ) -> (ConsentDecision, ConsentReason) {
+        use ConsentDecision::{Defer, Deny};
 
         // Hard suspend: only ServiceClassBasic is allowed when consent is suspended.
-        if shard.consent_suspended && !request.is_essential_service {
-            return Self::deny_with_audit(shard, request, ai_state, ConsentReason::ConsentSuspended);
+        shard.poll_recovery(request.now);
+        if shard.consent_suspended() && !request.is_essential_service {
+            return (Deny, ConsentReason::ConsentSuspended);
         }
 
         // Prompt / payment rate caps.
-        if shard.prompts_last_hour >= shard.max_prompts_per_hour && !request.is_essential_service {
-            return Self::deny_with_audit(
-                shard,
-                request,
-                ai_state,
-                ConsentReason::PromptRateExceeded,
-            );
+        if shard.prompts_last_hour(request.now) >= shard.max_prompts_per_hour
+            && !request.is_essential_service
+        {
+            return (Deny, ConsentReason::PromptRateExceeded);
         }
 
-        if shard.payments_last_hour >= shard.max_payments_per_hour && !request.is_essential_service
+        if shard.payments_last_hour(request.now) >= shard.max_payments_per_hour
+            && !request.is_essential_service
         {
-            return Self::deny_with_audit(
-                shard,
-                request,
-                ai_state,
-                ConsentReason::PaymentRateExceeded,
-            );
+            return (Deny, ConsentReason::PaymentRateExceeded);
         }
 
         // Amount corridor: never auto-approve above max_auto_amount_mills.
         if request.amount_mills > shard.max_auto_amount_mills && !request.is_essential_service {
-            return Self::deny_with_audit(
-                shard,
-                request,
-                ai_state,
-                ConsentReason::AmountOverLimit,
-            );
+            return (Deny, ConsentReason::AmountOverLimit);
         }
 
-        // Corridor checks: S_t and L_t.
-        let ns = shard.neuro_state;
-        let within_s_corridor = ns.svalue >= ns.smin && ns.svalue <= ns.smax;
-        let within_load_band =
-            ns.loadvalue <= ns.loadmax && ns.loadvalue <= shard.max_cognitive_load;
+        // Daily corridor: essential services still count toward the total but are
+        // never refused because of it (no exclusion from basic services).
+        let spent_today = shard.daily_spend_mills(request.now);
+        if spent_today.saturating_add(request.amount_mills) > shard.max_daily_spend_mills
+            && !request.is_essential_service
+        {
+            return (Deny, ConsentReason::DailySpendExceeded);
+        }
 
+        // Freshness: a companion that stopped reporting is never read as consent.
+        if !shard.neuro_state_fresh(request.now) {
+            if request.is_essential_service {
+                return (Defer, ConsentReason::NeuroStateStale);
+            } else {
+                return (Deny, ConsentReason::NeuroStateStale);
+            }
+        }
+
+        // Corridor checks: S_t and L_t.
         // If outside safe corridor, mark suspended for non-basics.
-        if !within_s_corridor || !within_load_band {
+        if !shard.within_corridor() {
             if !request.is_essential_service {
-                shard.consent_suspended = true;
-                return Self::deny_with_audit(
-                    shard,
-                    request,
-                    ai_state,
-                    ConsentReason::StateOutsideCorridor,
-                );
+                shard.suspend_consent(request.now);
+                return (Deny, ConsentReason::StateOutsideCorridor);
             } else {
                 // Essential service: defer until state stabilizes instead of forcing.
-                return Self::defer_with_audit(
-                    shard,
-                    request,
-                    ai_state,
-                    ConsentReason::EssentialStateUnstable,
-                );
+                return (Defer, ConsentReason::EssentialStateUnstable);
             }
         }
 
-        // Stability time: require S_t to stay in corridor long enough.
-        let now = request.now;
-        let stable_enough = match shard.stable_since {
-            Some(t0) => {
-                if let Ok(delta) = now.duration_since(t0) {
-                    delta >= shard.min_stability_time
-                } else {
-                    false
-                }
-            }
-            None => false,
-        };
-
-        if !stable_enough {
-            // Initialize or update stable_since; still defer this attempt.
-            shard.stable_since = Some(now);
-            return Self::defer_with_audit(
-                shard,
-                request,
-                ai_state,
-                ConsentReason::StabilityNotYetEstablished,
-            );
+        // A sample outside the exit band never authorizes a payment, even while
+        // the exit is still debounced: the debounce only keeps the stay alive.
+        if shard.stability.exit_pending() {
+            return (Defer, ConsentReason::CorridorExitPending);
         }
 
-        // AI consent macro state must confirm.
-        if ai_state != AiConsentState::Confirmed {
-            // Never guess; deny or defer depending on essential flag.
-            if request.is_essential_service {
-                return Self::defer_with_audit(
-                    shard,
-                    request,
-                    ai_state,
-                    ConsentReason::StabilityTimeInsufficient,
-                );
-            } else {
-                return Self::deny_with_audit(
-                    shard,
-                    request,
-                    ai_state,
-                    ConsentReason::StabilityTimeInsufficient,
-                );
-            }
+        // Stability time: require S_t to have dwelt in corridor long enough,
+        // as measured from the companion's stream by `update_neuro_state`.
+        // An evaluation stamped before the entry can't vouch for any dwell.
+        if shard.stable_since().is_some_and(|entered| request.now < entered) {
+            return (Defer, ConsentReason::StabilityTimeInvalid);
+        }
+        match shard.stability.dwell(request.now) {
+            Some(dwell) if dwell >= policy.min_stability_time() => {}
+            Some(_) => return (Defer, ConsentReason::StabilityTimeInsufficient),
+            None => return (Defer, ConsentReason::StabilityNotYetEstablished),
         }
 
-        // Conservative policy: block when risk scores are too high.
-        if shard.ai_consent_policy == AiConsentPolicy::Conservative {
-            if shard.r_fraud > 0.5 || shard.r_privacy > 0.5 || shard.r_tracking > 0.5 {
-                return Self::deny_with_audit(
-                    shard,
-                    request,
-                    ai_state,
-                    ConsentReason::RiskScoresTooHigh,
-                );
-            }
+        // AI consent confirmation and risk ceilings are up to the policy.
+        policy.assess(&PolicyContext {
+            shard,
+            request,
+            ai_state,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at, pay, sample, shard, steady};
+
+    /// 1_790_035_200 is 00:00:00 UTC.
+    const MIDNIGHT: u64 = 35_200;
+
+    fn eval(
+        shard: &mut AugFingerprintShard,
+        request: &PaymentRequest,
+    ) -> (ConsentDecision, ConsentReason) {
+        let (decision, reason, _) = AugFingerprintGuard::evaluate(shard, request, None);
+        (decision, reason)
+    }
+
+    /// Spend the whole 200.000 USD daily corridor in the last minutes before midnight.
+    fn spend_daily_cap_before_midnight(shard: &mut AugFingerprintShard) {
+        for t in [MIDNIGHT - 240, MIDNIGHT - 180, MIDNIGHT - 120, MIDNIGHT - 60] {
+            steady(shard, t);
+            let allowed = eval(shard, &pay(50_000, false, t));
+            assert_eq!(allowed, (ConsentDecision::Allow, ConsentReason::Ok));
         }
+        assert_eq!(shard.daily_spend_mills(at(MIDNIGHT - 1)), 200_000);
+    }
 
-        // All checks passed: allow and update counters.
-        shard.payments_last_hour = shard.payments_last_hour.saturating_add(1);
-        shard.prompts_last_hour = shard.prompts_last_hour.saturating_add(1);
+    #[test]
+    fn daily_spend_carries_over_utc_midnight() {
+        let mut shard = shard();
+        spend_daily_cap_before_midnight(&mut shard);
+
+        steady(&mut shard, MIDNIGHT + 30);
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, MIDNIGHT + 30)),
+            (ConsentDecision::Deny, ConsentReason::DailySpendExceeded)
+        );
+        // Essential services count but are never refused for the daily corridor.
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, true, MIDNIGHT + 30)),
+            (ConsentDecision::Allow, ConsentReason::Ok)
+        );
+
+        // The first 50.000 leaves the window 24h after it was spent.
+        let t = MIDNIGHT - 240 + 86_400;
+        steady(&mut shard, t);
+        assert_eq!(shard.daily_spend_mills(at(t)), 151_000);
+        let allowed = eval(&mut shard, &pay(49_000, false, t));
+        assert_eq!(allowed, (ConsentDecision::Allow, ConsentReason::Ok));
+    }
 
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Allow,
-                reason: ConsentReason::Ok,
-                timestamp: request.now,
-                s_value: shard.neuro_state.svalue,
-                load_value: shard.neuro_state.loadvalue,
-                ai_consent_state: ai_state,
-            })
-        } else {
-            None
-        };
+    #[test]
+    fn clock_stepping_back_does_not_free_daily_budget() {
+        let mut shard = shard();
+        spend_daily_cap_before_midnight(&mut shard);
+
+        let t = MIDNIGHT - 3_600;
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, t)),
+            (ConsentDecision::Deny, ConsentReason::DailySpendExceeded)
+        );
+        assert_eq!(shard.daily_spend_mills(at(t)), 200_000);
+    }
 
-        (ConsentDecision::Allow, ConsentReason::Ok, audit)
+    #[test]
+    fn denied_and_deferred_prompts_use_the_hourly_prompt_budget() {
+        let mut shard = shard();
+        shard.max_prompts_per_hour = 3;
+        steady(&mut shard, 100);
+
+        let over_limit = pay(shard.max_auto_amount_mills + 1, false, 100);
+        assert_eq!(eval(&mut shard, &over_limit).1, ConsentReason::AmountOverLimit);
+        assert_eq!(eval(&mut shard, &over_limit).1, ConsentReason::AmountOverLimit);
+        // Essential payment while out of corridor: deferred, still a prompt.
+        shard.update_neuro_state(sample(0.9, 102));
+        shard.update_neuro_state(sample(0.9, 105));
+        assert_eq!(eval(&mut shard, &pay(1_000, true, 105)).0, ConsentDecision::Defer);
+        assert_eq!(shard.prompts_last_hour(at(105)), 3);
+
+        steady(&mut shard, 200);
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, 200)),
+            (ConsentDecision::Deny, ConsentReason::PromptRateExceeded)
+        );
+        // Refused taps surfaced no prompt and don't extend the lockout.
+        assert_eq!(shard.prompts_last_hour(at(200)), 3);
+        steady(&mut shard, 3_705);
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, 3_705)),
+            (ConsentDecision::Allow, ConsentReason::Ok)
+        );
     }
 
-    fn deny_with_audit(
-        shard: &AugFingerprintShard,
-        request: &PaymentRequest,
-        ai_state: AiConsentState,
-        reason: ConsentReason,
-    ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Deny,
-                reason,
-                timestamp: request.now,
-                s_value: shard.neuro_state.svalue,
-                load_value: shard.neuro_state.loadvalue,
-                ai_consent_state: ai_state,
-            })
-        } else {
-            None
-        };
+    #[test]
+    fn prompt_accounting_selects_which_outcomes_are_charged() {
+        let mut shard = shard();
+        shard.prompt_accounting = PromptAccounting::AllowAndDefer;
+        steady(&mut shard, 100);
+
+        let over_limit = pay(shard.max_auto_amount_mills + 1, false, 100);
+        assert_eq!(eval(&mut shard, &over_limit).0, ConsentDecision::Deny);
+        assert_eq!(shard.prompts_last_hour(at(100)), 0);
+        assert_eq!(eval(&mut shard, &pay(1_000, false, 100)).0, ConsentDecision::Allow);
+        assert_eq!(shard.prompts_last_hour(at(100)), 1);
+
+        shard.prompt_accounting = PromptAccounting::AllowOnly;
+        shard.update_neuro_state(sample(0.9, 102));
+        shard.update_neuro_state(sample(0.9, 105));
+        assert_eq!(eval(&mut shard, &pay(1_000, true, 105)).0, ConsentDecision::Defer);
+        assert_eq!(shard.prompts_last_hour(at(105)), 1);
+    }
 
-        (ConsentDecision::Deny, reason, audit)
+    #[test]
+    fn stale_neuro_state_is_never_read_as_consent() {
+        let mut shard = shard();
+        steady(&mut shard, 100);
+        assert_eq!(eval(&mut shard, &pay(1_000, false, 130)).0, ConsentDecision::Allow);
+
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, 131)),
+            (ConsentDecision::Deny, ConsentReason::NeuroStateStale)
+        );
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, true, 131)),
+            (ConsentDecision::Defer, ConsentReason::NeuroStateStale)
+        );
+        // Staleness is not a corridor exit: consent is not suspended for it.
+        assert!(!shard.consent_suspended());
     }
 
-    fn defer_with_audit(
-        shard: &AugFingerprintShard,
-        request: &PaymentRequest,
-        ai_state: AiConsentState,
-        reason: ConsentReason,
-    ) -> (ConsentDecision, ConsentReason, Option<ConsentAuditRecord>) {
-        let audit = if shard.consent_audit_log_enabled {
-            Some(ConsentAuditRecord {
-                wallet_did: shard.wallet_did.clone(),
-                merchant_id: request.merchant_id.clone(),
-                amount_mills: request.amount_mills,
-                decision: ConsentDecision::Defer,
-                reason,
-                timestamp: request.now,
-                s_value: shard.neuro_state.svalue,
-                load_value: shard.neuro_state.loadvalue,
-                ai_consent_state: ai_state,
-            })
-        } else {
-            None
-        };
+    #[test]
+    fn reporting_gap_restarts_the_stability_run() {
+        let mut shard = shard();
+        steady(&mut shard, 100);
+        shard.update_neuro_state(sample(0.5, 200));
+        assert_eq!(shard.stable_since(), Some(at(200)));
+        assert_eq!(
+            eval(&mut shard, &pay(1_000, false, 202)),
+            (ConsentDecision::Defer, ConsentReason::StabilityTimeInsufficient)
+        );
+    }
 
-        (ConsentDecision::Defer, reason, audit)
+    #[test]
+    fn sample_outside_the_exit_band_defers_during_the_debounce() {
+        let mut shard = shard();
+        steady(&mut shard, 100);
+        shard.update_neuro_state(sample(0.98, 101));
+        assert!(shard.within_corridor());
+
+        let request = pay(1_000, false, 101);
+        let outcome = AugFingerprintGuard::evaluate_outcome(&mut shard, &request, None);
+        assert_eq!(outcome.decision, ConsentDecision::Defer);
+        assert_eq!(outcome.reason, ConsentReason::CorridorExitPending);
+        assert_eq!(outcome.retry_after, Some(Duration::from_secs(3)));
+        assert!(!shard.consent_suspended());
+
+        // Back inside before the debounce ran out: the stay and its dwell survive.
+        shard.update_neuro_state(sample(0.5, 102));
+        let allowed = eval(&mut shard, &pay(1_000, false, 102));
+        assert_eq!(allowed, (ConsentDecision::Allow, ConsentReason::Ok));
     }
 }
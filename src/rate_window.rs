@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Timestamped sliding window used for every "per hour" / "per day" cap.
+///
+/// Unlike a fixed window that is zeroed once an hour has elapsed, every event
+/// stays counted for exactly `window` after it happened, so a cap of N can never
+/// be exceeded across a boundary. Time is always passed in by the caller.
+///
+/// Events stamped after `now` (wall clock stepped backwards) keep counting until
+/// they are a full window old, so clock skew can never free up budget early.
+#[derive(Debug, Clone)]
+pub struct SlidingWindow {
+    window: Duration,
+    /// (event time, weight) in insertion order.
+    events: VecDeque<(SystemTime, u64)>,
+}
+
+impl SlidingWindow {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            events: VecDeque::new(),
+        }
+    }
+
+    pub fn hourly() -> Self {
+        Self::new(Duration::from_secs(3600))
+    }
+
+    pub fn daily() -> Self {
+        Self::new(Duration::from_secs(24 * 3600))
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    fn live(&self, t: SystemTime, now: SystemTime) -> bool {
+        match now.duration_since(t) {
+            Ok(age) => age < self.window,
+            Err(_) => true,
+        }
+    }
+
+    /// Drop events that have left the window.
+    pub fn prune(&mut self, now: SystemTime) {
+        let window = self.window;
+        self.events.retain(|&(t, _)| match now.duration_since(t) {
+            Ok(age) => age < window,
+            Err(_) => true,
+        });
+    }
+
+    /// Number of events inside the window ending at `now`.
+    pub fn count(&self, now: SystemTime) -> u32 {
+        let n = self
+            .events
+            .iter()
+            .filter(|&&(t, _)| self.live(t, now))
+            .count();
+        u32::try_from(n).unwrap_or(u32::MAX)
+    }
+
+    /// Sum of event weights inside the window ending at `now`.
+    pub fn total(&self, now: SystemTime) -> u64 {
+        self.events
+            .iter()
+            .filter(|&&(t, _)| self.live(t, now))
+            .fold(0u64, |acc, &(_, w)| acc.saturating_add(w))
+    }
+
+    /// Record one event of weight 1.
+    pub fn record(&mut self, now: SystemTime) {
+        self.record_weighted(now, 1);
+    }
+
+    /// Record one event carrying `weight` (e.g. an amount in mills).
+    pub fn record_weighted(&mut self, now: SystemTime, weight: u64) {
+        self.prune(now);
+        self.events.push_back((now, weight));
+    }
+
+    /// Earliest time at which `count` drops below `limit`.
+    ///
+    /// `None` if it already is, or if `limit` is zero and no slot will ever open.
+    pub fn next_slot_at(&self, now: SystemTime, limit: u32) -> Option<SystemTime> {
+        if limit == 0 {
+            return None;
+        }
+        let mut live: Vec<SystemTime> = self
+            .events
+            .iter()
+            .filter(|&&(t, _)| self.live(t, now))
+            .map(|&(t, _)| t)
+            .collect();
+        let live_count = u32::try_from(live.len()).unwrap_or(u32::MAX);
+        if live_count < limit {
+            return None;
+        }
+        live.sort();
+        // Once the (count - limit + 1) oldest events expire we are back under the cap.
+        let idx = (live_count - limit) as usize;
+        let expires = live[idx] + self.window;
+        Some(if expires > now { expires } else { now })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::at;
+
+    /// 1_790_035_200 is 00:00:00 UTC.
+    const MIDNIGHT: u64 = 35_200;
+
+    #[test]
+    fn daily_total_does_not_reset_at_utc_midnight() {
+        let mut spend = SlidingWindow::daily();
+        spend.record_weighted(at(MIDNIGHT - 30), 150_000);
+        spend.record_weighted(at(MIDNIGHT - 1), 40_000);
+
+        assert_eq!(spend.total(at(MIDNIGHT)), 190_000);
+        assert_eq!(spend.total(at(MIDNIGHT + 30)), 190_000);
+        // Each amount leaves exactly 24h after it was spent.
+        assert_eq!(spend.total(at(MIDNIGHT - 30 + 86_400)), 40_000);
+        assert_eq!(spend.total(at(MIDNIGHT - 1 + 86_400)), 0);
+    }
+
+    #[test]
+    fn events_after_now_keep_counting_when_the_clock_steps_back() {
+        let mut spend = SlidingWindow::daily();
+        spend.record_weighted(at(MIDNIGHT + 60), 100_000);
+
+        // Wall clock stepped back an hour: the future-stamped spend still counts.
+        assert_eq!(spend.total(at(MIDNIGHT + 60 - 3_600)), 100_000);
+        spend.record_weighted(at(MIDNIGHT), 5_000);
+        assert_eq!(spend.total(at(MIDNIGHT)), 105_000);
+
+        // Pruning at the earlier time must not drop it either.
+        spend.prune(at(MIDNIGHT - 3_600));
+        assert_eq!(spend.total(at(MIDNIGHT + 60)), 105_000);
+    }
+
+    #[test]
+    fn burst_across_the_old_hour_boundary_is_capped_at_n() {
+        let cap = 6;
+        let mut payments = SlidingWindow::hourly();
+        let mut allowed = 0;
+        // N at the end of one fixed hour, N more at the start of the next:
+        // the old reset would have let 2N through in two minutes.
+        for t in (3_540..3_600).step_by(10).chain((3_600..3_660).step_by(10)) {
+            if payments.count(at(t)) < cap {
+                payments.record(at(t));
+                allowed += 1;
+            }
+        }
+        assert_eq!(allowed, cap);
+        assert_eq!(payments.count(at(3_659)), cap);
+    }
+
+    #[test]
+    fn entries_expire_a_full_window_after_they_were_recorded() {
+        let mut prompts = SlidingWindow::hourly();
+        prompts.record(at(0));
+        prompts.record(at(600));
+
+        assert_eq!(prompts.count(at(3_599)), 2);
+        assert_eq!(prompts.count(at(3_600)), 1);
+        assert_eq!(prompts.count(at(4_200)), 0);
+
+        // Recording prunes what has left the window.
+        prompts.record(at(4_200));
+        assert_eq!(prompts.events.len(), 1);
+    }
+
+    #[test]
+    fn next_slot_opens_when_the_oldest_excess_event_expires() {
+        let mut payments = SlidingWindow::hourly();
+        for t in [0, 100, 200] {
+            payments.record(at(t));
+        }
+        assert_eq!(payments.next_slot_at(at(300), 4), None);
+        assert_eq!(payments.next_slot_at(at(300), 3), Some(at(3_600)));
+        assert_eq!(payments.next_slot_at(at(300), 2), Some(at(3_700)));
+        assert_eq!(payments.next_slot_at(at(300), 0), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::{Duration, SystemTime};
+
+use crate::paycomp_augfingerprint_guard::{AugFingerprintShard, ConsentReason};
+
+/// Recommended wait before a deferred prompt is shown again.
+///
+/// The longest of: what the deferral itself needs (`wait`), the time until the
+/// prompt window has a free slot again (`next_prompt_slot`) and the top of the
+/// host's latency band, so a single re-prompt lands when it can actually succeed
+/// and never faster than the host can comfortably answer.
+pub fn retry_hint(
+    wait: Duration,
+    next_prompt_slot: Option<SystemTime>,
+    latency_ms_max: u64,
+    now: SystemTime,
+) -> Duration {
+    let slot_wait = next_prompt_slot
+        .and_then(|at| at.duration_since(now).ok())
+        .unwrap_or(Duration::ZERO);
+    wait.max(slot_wait).max(Duration::from_millis(latency_ms_max))
+}
+
+impl AugFingerprintShard {
+    /// Retry hint for a `Defer` with `reason` at `now`, under a policy that needs
+    /// `min_stability_time` of corridor dwell.
+    ///
+    /// Corridor and freshness deferrals need a full dwell after the host is back
+    /// in corridor; a short dwell needs only the remainder, and an exit still being
+    /// debounced needs the debounce to settle it either way. Everything else waits
+    /// on the host or caregiver, so only the prompt window and latency band apply.
+    pub fn retry_after(
+        &self,
+        reason: ConsentReason,
+        min_stability_time: Duration,
+        now: SystemTime,
+    ) -> Duration {
+        let wait = match reason {
+            ConsentReason::StabilityTimeInsufficient
+            | ConsentReason::StabilityTimeInvalid
+            | ConsentReason::StabilityNotYetEstablished => {
+                let dwell = self.stability.dwell(now).unwrap_or(Duration::ZERO);
+                min_stability_time.saturating_sub(dwell)
+            }
+            ConsentReason::EssentialStateUnstable | ConsentReason::NeuroStateStale => {
+                min_stability_time
+            }
+            ConsentReason::CorridorExitPending => self.stability.exit_debounce,
+            _ => Duration::ZERO,
+        };
+        let next_prompt_slot = self.prompts_window.next_slot_at(now, self.max_prompts_per_hour);
+        retry_hint(wait, next_prompt_slot, self.latency_ms_max, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paycomp_augfingerprint_guard::{
+        AiConsentState, AugFingerprintGuard, ConsentDecision,
+    };
+    use crate::test_support::{at, pay, sample, shard};
+
+    #[test]
+    fn hint_is_the_longest_of_wait_slot_and_latency_band() {
+        let now = at(0);
+        let secs = Duration::from_secs;
+        assert_eq!(retry_hint(secs(1), None, 3_000, now), secs(3));
+        assert_eq!(retry_hint(secs(10), None, 3_000, now), secs(10));
+        assert_eq!(retry_hint(secs(1), Some(at(60)), 3_000, now), secs(60));
+        // A slot already open doesn't add anything.
+        assert_eq!(retry_hint(secs(5), Some(at(0) - secs(1)), 3_000, now), secs(5));
+    }
+
+    #[test]
+    fn short_dwell_waits_only_for_the_remainder() {
+        let mut shard = shard();
+        shard.latency_ms_max = 0;
+        shard.update_neuro_state(sample(0.5, 0));
+        shard.update_neuro_state(sample(0.5, 2));
+
+        let outcome = AugFingerprintGuard::evaluate_outcome(&mut shard, &pay(1_000, true, 2), None);
+        assert_eq!(outcome.reason, ConsentReason::StabilityTimeInsufficient);
+        assert_eq!(outcome.retry_after, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn out_of_corridor_waits_a_full_dwell_and_a_free_prompt_slot() {
+        let mut shard = shard();
+        shard.max_prompts_per_hour = 1;
+        shard.update_neuro_state(sample(0.9, 0));
+
+        let first = AugFingerprintGuard::evaluate_outcome(&mut shard, &pay(1_000, true, 0), None);
+        assert_eq!(first.reason, ConsentReason::EssentialStateUnstable);
+        // The deferral itself used the only prompt slot of the hour.
+        assert_eq!(first.retry_after, Some(Duration::from_secs(3_600)));
+
+        shard.max_prompts_per_hour = 10;
+        let second = AugFingerprintGuard::evaluate_outcome(&mut shard, &pay(1_000, true, 0), None);
+        assert_eq!(second.retry_after, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn tuple_entry_points_match_the_outcome() {
+        let mut a = shard();
+        a.update_neuro_state(sample(0.5, 0));
+        a.update_neuro_state(sample(0.5, 20));
+        let mut b = a.clone();
+
+        let request = pay(1_000, true, 20);
+        let confirmed = AiConsentState::Confirmed;
+        let (decision, reason, audit) =
+            AugFingerprintGuard::evaluate_payment(&mut a, &request, confirmed);
+        let outcome = AugFingerprintGuard::evaluate_with_hint(&mut b, &request, confirmed);
+        assert_eq!((decision, reason), (outcome.decision, outcome.reason));
+        assert_eq!(decision, ConsentDecision::Allow);
+        assert_eq!((audit.is_some(), outcome.retry_after), (true, None));
+    }
+}
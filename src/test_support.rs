@@ -0,0 +1,82 @@
+//! Fixtures shared by the in-file unit tests.
+//!
+//! One host wallet, one companion corridor (S_t 0.4–0.6, L_t ≤ 0.5) and one POS
+//! corridor; tests that need something else override fields on these.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::augfingerprint_corridor::{
+    AuStatus, AugFingerprintCorridor, InterfacePrimary, PreferredConsentMode,
+};
+use crate::neuro_corridor::{CorridorBand, NeuroCorridor};
+use crate::paycomp_augfingerprint_guard::{AugFingerprintShard, NeuroState, PaymentRequest};
+
+pub(crate) const HOST_DID: &str = "did:aln:host";
+
+/// `secs` after the test epoch.
+pub(crate) fn at(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_790_000_000 + secs)
+}
+
+/// Companion report of `svalue` at low load against the shared corridor.
+pub(crate) fn sample(svalue: f32, t: u64) -> NeuroState {
+    NeuroState {
+        svalue,
+        loadvalue: 0.2,
+        smin: 0.4,
+        smax: 0.6,
+        loadmax: 0.5,
+        last_update: at(t),
+    }
+}
+
+/// Companion reports in corridor from `t - 20` to `t`.
+pub(crate) fn steady(shard: &mut AugFingerprintShard, t: u64) {
+    shard.update_neuro_state(sample(0.5, t - 20));
+    shard.update_neuro_state(sample(0.5, t));
+}
+
+/// Host wallet created at t=0 with the shard defaults.
+pub(crate) fn shard() -> AugFingerprintShard {
+    AugFingerprintShard::new(HOST_DID.into(), at(0))
+}
+
+/// Payment to merchant `m-1` in `phx` at `t`.
+pub(crate) fn pay(amount_mills: u64, is_essential_service: bool, t: u64) -> PaymentRequest {
+    PaymentRequest {
+        merchant_id: "m-1".into(),
+        region_id: "phx".into(),
+        amount_mills,
+        is_essential_service,
+        now: at(t),
+    }
+}
+
+/// The `sample` corridor as an entry band with 0.05 hysteresis on both edges.
+pub(crate) fn neuro_corridor() -> NeuroCorridor {
+    let entry = CorridorBand {
+        smin: 0.4,
+        smax: 0.6,
+        loadmax: 0.5,
+    };
+    NeuroCorridor::with_hysteresis(entry, 0.05, 0.05)
+}
+
+/// POS corridor: 500–3_000 ms, 10 prompts and 3 decisions per hour.
+pub(crate) fn pos_corridor() -> AugFingerprintCorridor {
+    AugFingerprintCorridor {
+        austatus: AuStatus::OrganicallyIntegratedAugmentedCitizen,
+        cap_input_speech: 0.3,
+        cap_input_internal_bio: 0.9,
+        latency_ms_min: 500,
+        latency_ms_max: 3_000,
+        max_decisions_per_hour: 3,
+        max_prompts_per_hour: 10,
+        max_informational_per_hour: 6,
+        max_low_value_payments_per_hour: 6,
+        high_stakes_threshold_mills: 50_000,
+        preferred_consent_mode: PreferredConsentMode::BciState,
+        interface_primary: InterfacePrimary::ImplantedNfc,
+        no_exclusion_basic_services: true,
+    }
+}